@@ -0,0 +1,153 @@
+//! Generates Rust definitions for the enums, entities and IntGrid values of the LDtk project,
+//! so the game fails to compile instead of misbehaving when the code and the editor disagree.
+//!
+//! The generated file is included by `src/unsorted/ldtk_definitions.rs`.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+use serde_json::Value;
+
+const PROJECT: &str = "assets/game.ldtk";
+/// The definitions mirror the whole project, so they include enums, entities and values the code doesn't use (yet).
+/// Put on each generated module, so unused code written by hand is still reported.
+const ALLOW_UNUSED: &str = "#[allow(dead_code)]\n";
+
+fn main() {
+    println!("cargo:rerun-if-changed={PROJECT}");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let json = fs::read_to_string(PROJECT).unwrap_or_else(|error| panic!("Couldn't read {PROJECT}: {error}"));
+    let project: Value = serde_json::from_str(&json).unwrap_or_else(|error| panic!("Couldn't parse {PROJECT}: {error}"));
+    let defs = &project["defs"];
+
+    let mut out = String::new();
+    write_enums(&mut out, array(&defs["enums"]));
+    write_entities(&mut out, array(&defs["entities"]));
+    write_int_grids(&mut out, array(&defs["layers"]));
+
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("ldtk_definitions.rs");
+    fs::write(path, out).expect("Couldn't write the LDtk definitions!");
+}
+
+fn write_enums(out: &mut String, enums: &[Value]) {
+    out.push_str("/// Enums of the LDtk project, the variants (de)serialize to their LDtk identifier.\n");
+    out.push_str(ALLOW_UNUSED);
+    out.push_str("pub mod enums {\n");
+    out.push_str("    pub trait EnumId { const ID: i32; }\n");
+    for definition in enums {
+        let identifier = string(&definition["identifier"]);
+        let uid = definition["uid"].as_i64().expect("LDtk enum without uid!");
+        let name = pascal_case(identifier);
+        let values = array(&definition["values"])
+            .iter()
+            .map(|value| {
+                let id = string(&value["id"]);
+                (pascal_case(id), id)
+            })
+            .collect::<Vec<_>>();
+
+        writeln!(out, "\n    /// `{identifier}` enum of the LDtk project.").unwrap();
+        out.push_str("    #[derive(bevy::reflect::Reflect, serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]\n");
+        writeln!(out, "    pub enum {name} {{").unwrap();
+        for (variant, id) in values.iter() {
+            if variant != id { writeln!(out, "        #[serde(rename = {id:?})]").unwrap(); }
+            writeln!(out, "        {variant},").unwrap();
+        }
+        out.push_str("    }\n\n");
+
+        writeln!(out, "    impl EnumId for {name} {{ const ID: i32 = {uid}; }}\n").unwrap();
+
+        writeln!(out, "    impl {name} {{").unwrap();
+        writeln!(out, "        pub const ALL: [Self; {}] = [{}];\n", values.len(),
+            values.iter().map(|(variant, _)| format!("Self::{variant}")).collect::<Vec<_>>().join(", ")).unwrap();
+        out.push_str("        /// Identifier of the value in the LDtk project.\n");
+        out.push_str("        pub const fn identifier(&self) -> &'static str {\n            match self {\n");
+        for (variant, id) in values.iter() {
+            writeln!(out, "                Self::{variant} => {id:?},").unwrap();
+        }
+        out.push_str("            }\n        }\n    }\n\n");
+
+        writeln!(out, "    impl std::str::FromStr for {name} {{").unwrap();
+        out.push_str("        type Err = ();\n\n");
+        out.push_str("        fn from_str(identifier: &str) -> Result<Self, Self::Err> {\n");
+        out.push_str("            Self::ALL.into_iter().find(|value| value.identifier() == identifier).ok_or(())\n");
+        out.push_str("        }\n    }\n");
+    }
+    out.push_str("}\n\n");
+}
+
+fn write_entities(out: &mut String, entities: &[Value]) {
+    out.push_str("/// Identifiers of the entities of the LDtk project.\n");
+    out.push_str(ALLOW_UNUSED);
+    out.push_str("pub mod entities {\n");
+    for definition in entities {
+        let identifier = string(&definition["identifier"]);
+        writeln!(out, "    pub const {}: &str = {identifier:?};", screaming_snake_case(identifier)).unwrap();
+    }
+    out.push_str("}\n\n");
+}
+
+fn write_int_grids(out: &mut String, layers: &[Value]) {
+    out.push_str("/// Values of the IntGrid layers of the LDtk project, a module per layer.\n");
+    out.push_str(ALLOW_UNUSED);
+    out.push_str("pub mod int_grid {\n");
+    for layer in layers.iter().filter(|layer| layer["__type"] == "IntGrid") {
+        let identifier = string(&layer["identifier"]);
+        writeln!(out, "    pub mod {} {{", snake_case(identifier)).unwrap();
+        writeln!(out, "        pub const IDENTIFIER: &str = {identifier:?};").unwrap();
+        for value in array(&layer["intGridValues"]) {
+            let number = value["value"].as_i64().expect("LDtk IntGrid value without value!");
+            let name = match value["identifier"].as_str() {
+                Some(name) if !name.is_empty() => screaming_snake_case(name),
+                _ => format!("VALUE_{number}"),
+            };
+            writeln!(out, "        pub const {name}: i32 = {number};").unwrap();
+        }
+        out.push_str("    }\n");
+    }
+    out.push_str("}\n");
+}
+
+fn array(value: &Value) -> &[Value] {
+    value.as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+fn string(value: &Value) -> &str {
+    value.as_str().expect("LDtk identifier isn't a string!")
+}
+
+/// Splits `Healing_Plant`, `TopSlabThick` and `wall shadows` into their words.
+fn words(identifier: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for character in identifier.chars() {
+        if !character.is_alphanumeric() {
+            if !word.is_empty() { words.push(std::mem::take(&mut word)); }
+            continue;
+        }
+        if character.is_uppercase() && word.chars().last().is_some_and(|last| !last.is_uppercase()) {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(character);
+    }
+    if !word.is_empty() { words.push(word); }
+    words
+}
+
+fn pascal_case(identifier: &str) -> String {
+    words(identifier)
+        .iter()
+        .map(|word| {
+            let mut characters = word.chars();
+            characters.next().map(|first| first.to_uppercase().chain(characters).collect::<String>()).unwrap_or_default()
+        })
+        .collect()
+}
+
+fn screaming_snake_case(identifier: &str) -> String {
+    words(identifier).iter().map(|word| word.to_uppercase()).collect::<Vec<_>>().join("_")
+}
+
+fn snake_case(identifier: &str) -> String {
+    words(identifier).iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("_")
+}
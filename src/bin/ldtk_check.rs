@@ -0,0 +1,285 @@
+//! Checks the LDtk project for problems before the game is launched.
+//!
+//! `cargo run --bin ldtk_check [path to project]` prints a JSON report and exits with 1 when it contains errors.
+//! Warnings are reported as well, but they don't fail the check.
+
+use std::{collections::{HashSet, VecDeque}, fs, path::{Path, PathBuf}, process::ExitCode};
+
+use bevy_ecs_ldtk::{ldtk::{EntityInstance, LayerInstance, LdtkJson, Level, TilesetDefinition}, prelude::LdtkFields};
+use serde::Serialize;
+
+mod ldtk {
+    include!(concat!(env!("OUT_DIR"), "/ldtk_definitions.rs"));
+}
+#[allow(dead_code)]
+#[path = "../unsorted/tile_metadata.rs"]
+mod tile_metadata;
+
+use ldtk::{entities, enums::{self, EnumId}, int_grid::collisions};
+use tile_metadata::TileMetadataParsed;
+
+const PROJECT: &str = "assets/game.ldtk";
+/// Entities the game registers with `register_ldtk_entity` or `register_ldtk_entity_with_promise`.
+/// Add an entity here when registering it in the game, otherwise the check reports it as unregistered.
+const REGISTERED_ENTITIES: [&str; 7] = [
+    entities::PLAYER,
+    entities::MOB,
+    entities::FINISH,
+    entities::CHECKPOINT,
+    entities::PICKUP,
+    entities::CHEST,
+    entities::DOOR,
+];
+/// `dirt` and `stone` cells are solid, the same as in the `LevelColliderPlugin`.
+const SOLID_INT_CELLS: [i32; 2] = [collisions::DIRT, collisions::STONE];
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Serialize)]
+struct Problem {
+    severity: Severity,
+    check: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    level: Option<String>,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct Report {
+    project: String,
+    errors: usize,
+    warnings: usize,
+    problems: Vec<Problem>,
+}
+
+#[derive(Default)]
+struct Problems(Vec<Problem>);
+
+impl Problems {
+    fn error(&mut self, check: &'static str, level: Option<&Level>, message: String) {
+        self.push(Severity::Error, check, level, message);
+    }
+
+    fn warning(&mut self, check: &'static str, level: Option<&Level>, message: String) {
+        self.push(Severity::Warning, check, level, message);
+    }
+
+    fn push(&mut self, severity: Severity, check: &'static str, level: Option<&Level>, message: String) {
+        self.0.push(Problem { severity, check, level: level.map(|level| level.identifier.clone()), message });
+    }
+}
+
+fn main() -> ExitCode {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let path = std::env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| root.join(PROJECT));
+
+    let mut problems = Problems::default();
+    match load_project(&path) {
+        Ok(project) => check_project(&project, &mut problems),
+        Err(message) => problems.error("project", None, message),
+    }
+
+    let count = |severity| problems.0.iter().filter(|problem| problem.severity == severity).count();
+    let report = Report {
+        project: path.display().to_string(),
+        errors: count(Severity::Error),
+        warnings: count(Severity::Warning),
+        problems: problems.0,
+    };
+    println!("{}", serde_json::to_string_pretty(&report).expect("The report is always valid JSON!"));
+
+    if report.errors > 0 { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
+
+fn load_project(path: &Path) -> Result<LdtkJson, String> {
+    let json = fs::read_to_string(path).map_err(|error| format!("Couldn't read {}: {}", path.display(), error))?;
+    serde_json::from_str(&json).map_err(|error| format!("Couldn't parse {}: {}", path.display(), error))
+}
+
+fn check_project(project: &LdtkJson, problems: &mut Problems) {
+    for tileset in project.defs.tilesets.iter() {
+        check_tile_metadata(tileset, problems);
+        check_enum_tags(tileset, problems);
+    }
+
+    for level in project.levels.iter() {
+        let instances = level_entities(level).collect::<Vec<_>>();
+
+        let mut unregistered = HashSet::new();
+        for (_, entity) in instances.iter() {
+            if !REGISTERED_ENTITIES.contains(&entity.identifier.as_str()) && unregistered.insert(&entity.identifier) {
+                problems.error("unregistered_entity", Some(level), format!("`{}` isn't registered with `register_ldtk_entity`, it won't be spawned", entity.identifier));
+            }
+        }
+
+        for (layer, mob) in instances.iter().filter(|(_, entity)| entity.identifier == entities::MOB) {
+            check_patrol(level, layer, mob, problems);
+            check_projectile(level, mob, &project.defs.tilesets, problems);
+        }
+
+        let start = player_start(level, &instances);
+        if start.is_none() {
+            problems.warning("missing_player", Some(level), String::from("Level has no `Player` entity and no `player_start`, it can only be entered from a neighbouring level"));
+        }
+
+        let finishes = instances.iter().filter(|(_, entity)| entity.identifier == entities::FINISH).collect::<Vec<_>>();
+        if finishes.is_empty() {
+            problems.warning("missing_finish", Some(level), String::from("Level has no `Finish` entity, it can only be left through a neighbouring level"));
+        }
+
+        if let Some(start) = start {
+            let free = FreeCells::from_level(level, &project.defs.tilesets, project.default_grid_size);
+            let reachable = free.reachable_from(start);
+            for (_, finish) in finishes {
+                let cell = grid(finish);
+                if !free.is_free(cell) {
+                    problems.error("unreachable_finish", Some(level), format!("`Finish` at {:?} is inside a wall", cell));
+                } else if !reachable.contains(&cell) {
+                    problems.error("unreachable_finish", Some(level), format!("`Finish` at {:?} can't be reached from the player start at {:?}", cell, start));
+                }
+            }
+        }
+    }
+}
+
+fn check_tile_metadata(tileset: &TilesetDefinition, problems: &mut Problems) {
+    for entry in tileset.custom_data.iter() {
+        if let Err(error) = TileMetadataParsed::parse(&entry.data) {
+            problems.error("tile_metadata", None, format!("Tile {} of tileset `{}` has malformed metadata {:?}: {}", entry.tile_id, tileset.identifier, entry.data, error));
+        }
+    }
+}
+
+fn check_enum_tags(tileset: &TilesetDefinition, problems: &mut Problems) {
+    let Some(enum_uid) = tileset.tags_source_enum_uid else { return; };
+    let used_tags = tileset.enum_tags.iter().filter(|tag| !tag.tile_ids.is_empty()).map(|tag| &tag.enum_value_id);
+
+    // the collider tags are the only ones the tag handler knows about
+    for tag in used_tags {
+        if enum_uid != enums::Collider::ID {
+            problems.error("unknown_enum_tag", None, format!("Tileset `{}` is tagged with `{}` of the enum {}, which the tag handler doesn't handle", tileset.identifier, tag, enum_uid));
+        } else if tag.parse::<enums::Collider>().is_err() {
+            problems.error("unknown_enum_tag", None, format!("Tileset `{}` is tagged with the unknown collider `{}`", tileset.identifier, tag));
+        }
+    }
+}
+
+fn check_patrol(level: &Level, layer: &LayerInstance, mob: &EntityInstance, problems: &mut Problems) {
+    let points = mob.get_maybe_points_field("patrol").unwrap_or_default();
+    // walking mobs without patrol points wander along the ground
+    let walking = mob.get_maybe_enum_field("movement").ok().and_then(Option::as_deref) == Some(enums::Movement::Walking.identifier());
+    if points.is_empty() && !walking {
+        problems.warning("patrol", Some(level), format!("`Mob` at {:?} has no patrol points, it will stand still", grid(mob)));
+    }
+    for point in points.iter().flatten() {
+        if point.x < 0 || point.y < 0 || point.x >= layer.c_wid || point.y >= layer.c_hei {
+            problems.error("patrol", Some(level), format!("`Mob` at {:?} has the patrol point {:?} outside of the level", grid(mob), point));
+        }
+    }
+}
+
+/// The projectile sprite has to be a single tile inside a tileset the game loads.
+fn check_projectile(level: &Level, mob: &EntityInstance, tilesets: &[TilesetDefinition], problems: &mut Problems) {
+    let Ok(Some(tile)) = mob.get_maybe_tile_field("projectile") else { return; };
+    let (uid, x, y, w, h) = (tile.tileset_uid, tile.x, tile.y, tile.w, tile.h);
+    let Some(tileset) = tilesets.iter().find(|tileset| tileset.uid == uid) else {
+        problems.error("projectile", Some(level), format!("`Mob` at {:?} has a projectile from the unknown tileset {}", grid(mob), uid));
+        return;
+    };
+    if x < 0 || y < 0 || w <= 0 || h <= 0 || x + w > tileset.px_wid || y + h > tileset.px_hei {
+        problems.error("projectile", Some(level), format!("`Mob` at {:?} has a projectile outside of the tileset `{}`", grid(mob), tileset.identifier));
+    }
+    else if w > tileset.tile_grid_size || h > tileset.tile_grid_size {
+        problems.error("projectile", Some(level), format!("`Mob` at {:?} has a projectile of {}x{} pixels, larger than a tile of the tileset `{}`", grid(mob), w, h, tileset.identifier));
+    }
+}
+
+fn level_entities(level: &Level) -> impl Iterator<Item = (&LayerInstance, &EntityInstance)> {
+    level.layer_instances.iter().flatten().flat_map(|layer| layer.entity_instances.iter().map(move |entity| (layer, entity)))
+}
+
+/// LDtk grid coordinate of an entity (y pointing down).
+fn grid(entity: &EntityInstance) -> (i32, i32) {
+    (entity.grid.x, entity.grid.y)
+}
+
+/// The `player_start` of the level, the position of its `Player` entity otherwise.
+fn player_start(level: &Level, instances: &[(&LayerInstance, &EntityInstance)]) -> Option<(i32, i32)> {
+    level
+        .get_maybe_point_field("player_start")
+        .ok()
+        .copied()
+        .flatten()
+        .map(|start| (start.x, start.y))
+        .or_else(|| instances.iter().find(|(_, entity)| entity.identifier == entities::PLAYER).map(|(_, player)| grid(player)))
+}
+
+/// Cells of a level a character can be in, from the `Collisions` IntGrid layer and the tiles tagged `Solid`.
+struct FreeCells {
+    width: i32,
+    height: i32,
+    free: Vec<bool>,
+}
+
+impl FreeCells {
+    fn from_level(level: &Level, tilesets: &[TilesetDefinition], grid_size: i32) -> Self {
+        let (width, height) = (level.px_wid / grid_size, level.px_hei / grid_size);
+        let mut cells = Self { width, height, free: vec![true; (width * height).max(0) as usize] };
+
+        for layer in level.layer_instances.iter().flatten() {
+            if layer.identifier == collisions::IDENTIFIER {
+                for (index, value) in layer.int_grid_csv.iter().enumerate() {
+                    if SOLID_INT_CELLS.contains(value) {
+                        let index = index as i32;
+                        cells.set_solid((index % layer.c_wid, index / layer.c_wid));
+                    }
+                }
+            }
+
+            let Some(tileset) = layer.tileset_def_uid.and_then(|uid| tilesets.iter().find(|tileset| tileset.uid == uid)) else { continue; };
+            if tileset.tags_source_enum_uid != Some(enums::Collider::ID) { continue; }
+            let solid_tiles = tileset
+                .enum_tags
+                .iter()
+                .filter(|tag| tag.enum_value_id == enums::Collider::Solid.identifier())
+                .flat_map(|tag| tag.tile_ids.iter().copied())
+                .collect::<HashSet<_>>();
+            for tile in layer.grid_tiles.iter().chain(layer.auto_layer_tiles.iter()) {
+                if solid_tiles.contains(&tile.t) {
+                    cells.set_solid((tile.px.x / layer.grid_size, tile.px.y / layer.grid_size));
+                }
+            }
+        }
+        cells
+    }
+
+    fn index(&self, (x, y): (i32, i32)) -> Option<usize> {
+        (x >= 0 && y >= 0 && x < self.width && y < self.height).then_some((y * self.width + x) as usize)
+    }
+
+    fn set_solid(&mut self, cell: (i32, i32)) {
+        if let Some(index) = self.index(cell) { self.free[index] = false; }
+    }
+
+    fn is_free(&self, cell: (i32, i32)) -> bool {
+        self.index(cell).is_some_and(|index| self.free[index])
+    }
+
+    /// Flood fills the free cells from `start`. Jump heights aren't taken into account, so a finish that is
+    /// reachable here might still be out of reach, but a finish that isn't reachable here never is.
+    fn reachable_from(&self, start: (i32, i32)) -> HashSet<(i32, i32)> {
+        let mut reachable = HashSet::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(cell) = queue.pop_front() {
+            if !self.is_free(cell) || !reachable.insert(cell) { continue; }
+            let (x, y) = cell;
+            queue.extend([(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]);
+        }
+        reachable
+    }
+}
@@ -0,0 +1,59 @@
+use bevy::{
+    app::{
+        Plugin,
+        Startup,
+        Update
+    },
+    prelude::{
+        Camera2dBundle,
+        Commands,
+        Component,
+        GlobalTransform,
+        IntoSystemConfigs,
+        Query,
+        Res,
+        Transform,
+        With,
+        Without
+    },
+    time::Time
+};
+
+use crate::{character::PlayerTag, game_flow::Gameplay};
+
+const CAMERA_SCALE: f32 = 0.5;
+const FOLLOW_SPEED: f32 = 5.0;
+
+#[derive(Component)]
+pub struct MainCamera;
+
+pub struct CameraPlugin;
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .add_systems(Startup, spawn_camera)
+            .add_systems(Update, follow_player.in_set(Gameplay));
+    }
+}
+
+fn spawn_camera(mut commands: Commands) {
+    let mut camera = Camera2dBundle::default();
+    camera.projection.scale = CAMERA_SCALE;
+    camera.transform.translation.x += 1280.0 / 4.0 + 100.;
+    camera.transform.translation.y += 720.0 / 4.0;
+    commands.spawn((MainCamera, camera));
+}
+
+fn follow_player(
+    time: Res<Time>,
+    player: Query<&GlobalTransform, With<PlayerTag>>,
+    mut camera: Query<&mut Transform, (With<MainCamera>, Without<PlayerTag>)>,
+) {
+    let Ok(player) = player.get_single() else { return; };
+    let Ok(mut camera) = camera.get_single_mut() else { return; };
+
+    let target = player.translation().truncate();
+    let step = (FOLLOW_SPEED * time.delta_seconds()).min(1.0);
+    let position = camera.translation.truncate().lerp(target, step);
+    camera.translation = position.extend(camera.translation.z);
+}
@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Plugin, Update},
+    math::Vec2,
+    prelude::{BuildChildren, Commands, Component, DespawnRecursiveExt, Entity, IntoSystemConfigs, Query, Res, ResMut, Resource, SpatialBundle, Transform, Trigger, Without},
+    sprite::Sprite,
+    time::{Real, Time, Timer, TimerMode, Virtual}
+};
+use bevy_rapier2d::prelude::{Collider, CollisionEvent, CollisionGroups, Group};
+use leafwing_input_manager::prelude::ActionState;
+
+use crate::{
+    collision::LocalGroupNames,
+    enemies::{ColliderBundle, ObservableColliderBundle},
+    game_flow::Gameplay,
+    items::{Inventory, Item}
+};
+use super::{CharacterAction, Damage};
+
+/// Characters carrying a knife attack with it, the hitbox appears in front of them for a moment.
+pub struct AttackPlugin;
+impl Plugin for AttackPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<HitStop>()
+            .observe(hit_on_overlap)
+            .add_systems(Update, (attack, expire_hitboxes, tick_attack_cooldown).in_set(Gameplay))
+            // runs on real time, the game time is almost frozen during the hit-stop
+            .add_systems(Update, end_hit_stop);
+    }
+}
+
+const DAMAGE: u32 = 1;
+const COOLDOWN: Duration = Duration::from_millis(400);
+const HITBOX_LIFETIME: Duration = Duration::from_millis(120);
+const HITBOX_HALF_SIZE: Vec2 = Vec2::new(6.0, 6.0);
+/// Half the width of the player, the hitbox starts right where the player ends.
+const REACH_OFFSET: f32 = 4.0;
+const KNOCKBACK_VELOCITY: f32 = 100.0;
+
+/// How long and how much the game slows down when an attack lands.
+const HIT_STOP: Duration = Duration::from_millis(80);
+const HIT_STOP_SPEED: f32 = 0.05;
+
+/// The character can't attack again until it is removed.
+#[derive(Component, Debug)]
+struct AttackCooldown(Timer);
+
+#[derive(Component, Debug)]
+struct Hitbox {
+    lifetime: Timer,
+    /// `1.0` when attacking to the right.
+    direction: f32,
+    /// Everything hit by this attack, an attack hits each enemy once.
+    hit: Vec<Entity>,
+}
+
+#[derive(Resource, Debug, Default)]
+struct HitStop(Option<Timer>);
+
+fn attack(
+    mut commands: Commands,
+    characters: Query<(Entity, &ActionState<CharacterAction>, &Inventory, &Sprite), Without<AttackCooldown>>,
+) {
+    for (entity, input, inventory, sprite) in characters.iter() {
+        if !input.just_pressed(&CharacterAction::Attack) || !inventory.contains(Item::Knife) { continue; }
+
+        // the sprites of the characters face right
+        let direction = if sprite.flip_x { -1.0 } else { 1.0 };
+        commands
+            .entity(entity)
+            .insert(AttackCooldown(Timer::new(COOLDOWN, TimerMode::Once)))
+            .with_children(|children| {
+                children.spawn((
+                    Hitbox { lifetime: Timer::new(HITBOX_LIFETIME, TimerMode::Once), direction, hit: Vec::new() },
+                    SpatialBundle::from_transform(Transform::from_xyz(direction * (REACH_OFFSET + HITBOX_HALF_SIZE.x), 0.0, 0.0)),
+                    ObservableColliderBundle::from(ColliderBundle {
+                        collider: Collider::cuboid(HITBOX_HALF_SIZE.x, HITBOX_HALF_SIZE.y),
+                        collision_groups: CollisionGroups {
+                            memberships: Group::PLAYER_ATTACK,
+                            filters: Group::TEST_ENEMY,
+                        },
+                    }),
+                ));
+            });
+    }
+}
+
+fn hit_on_overlap(
+    trigger: Trigger<CollisionEvent>,
+    mut commands: Commands,
+    mut hitboxes: Query<&mut Hitbox>,
+    mut hit_stop: ResMut<HitStop>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let Ok(mut hitbox) = hitboxes.get_mut(trigger.entity()) else { return; };
+    let CollisionEvent::Started(first, second, _) = *trigger.event() else { return; };
+    let other = if first == trigger.entity() { second } else { first };
+    if hitbox.hit.contains(&other) { return; }
+    hitbox.hit.push(other);
+
+    let knockback = Vec2::new(hitbox.direction, 0.5) * KNOCKBACK_VELOCITY;
+    commands.trigger_targets(Damage { amount: DAMAGE, knockback }, other);
+
+    time.set_relative_speed(HIT_STOP_SPEED);
+    hit_stop.0 = Some(Timer::new(HIT_STOP, TimerMode::Once));
+}
+
+fn expire_hitboxes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut hitboxes: Query<(Entity, &mut Hitbox)>,
+) {
+    for (entity, mut hitbox) in hitboxes.iter_mut() {
+        if hitbox.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn tick_attack_cooldown(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut characters: Query<(Entity, &mut AttackCooldown)>,
+) {
+    for (entity, mut cooldown) in characters.iter_mut() {
+        if cooldown.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<AttackCooldown>();
+        }
+    }
+}
+
+fn end_hit_stop(
+    real_time: Res<Time<Real>>,
+    mut time: ResMut<Time<Virtual>>,
+    mut hit_stop: ResMut<HitStop>,
+) {
+    let Some(timer) = hit_stop.0.as_mut() else { return; };
+    if timer.tick(real_time.delta()).finished() {
+        time.set_relative_speed(1.0);
+        hit_stop.0 = None;
+    }
+}
@@ -0,0 +1,78 @@
+use bevy::{
+    app::Plugin,
+    ecs::{component::ComponentId, world::DeferredWorld},
+    app::Update,
+    prelude::{Bundle, Component, Entity, IntoSystemConfigs, Query, Res, With}
+};
+use bevy_ecs_ldtk::{app::LdtkIntCellAppExt, LdtkIntCell};
+use bevy_rapier2d::{plugin::RapierContext, prelude::{Collider, CollisionGroups, Group}};
+
+use crate::{collision::LocalGroupNames, enemies::{ColliderBundle, ObservableColliderBundle}, game_flow::Gameplay, unsorted::{int_grid::collisions, BevyPromiseResolver, Promise, PromiseProcedure}};
+
+const TILESIZE: f32 = 16.0;
+
+pub struct ClimbingPlugin;
+impl Plugin for ClimbingPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .register_ldtk_int_cell::<LadderBundle>(collisions::LADDER)
+            .register_promise::<LadderBundle>()
+            .add_systems(Update, count_ladder_contacts.before(super::player_movement).in_set(Gameplay));
+    }
+}
+
+#[derive(Default, Bundle, LdtkIntCell)]
+struct LadderBundle {
+    promise: Promise<Self>,
+    ladder: Ladder,
+}
+
+#[derive(Component, Default, Clone, Copy, Debug)]
+struct Ladder;
+
+impl PromiseProcedure for LadderBundle {
+    fn resolve_promise(mut world: DeferredWorld, entity: Entity, component_id: ComponentId) {
+        world
+            .commands()
+            .entity(entity)
+            .insert(ObservableColliderBundle::from(ColliderBundle {
+                collider: Collider::cuboid(TILESIZE / 2.0, TILESIZE / 2.0),
+                collision_groups: CollisionGroups {
+                    memberships: Group::LADDER,
+                    filters: Group::PLAYER,
+                },
+            }))
+            .remove_by_id(component_id);
+    }
+}
+
+/// Tracks the ladders a character touches and whether it is climbing them.
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct Climber {
+    ladders: u32,
+    pub climbing: bool,
+}
+
+impl Climber {
+    pub fn on_ladder(&self) -> bool { self.ladders > 0 }
+}
+
+/// Counts the ladders overlapping each climber every frame, so ladders despawned with their level aren't counted anymore.
+fn count_ladder_contacts(
+    rapier: Res<RapierContext>,
+    ladders: Query<(), With<Ladder>>,
+    mut climbers: Query<(Entity, &mut Climber)>,
+) {
+    for (entity, mut climber) in climbers.iter_mut() {
+        let ladders = rapier
+            .intersection_pairs_with(entity)
+            .filter(|(_, _, intersecting)| *intersecting)
+            .filter(|(first, second, _)| ladders.contains(if *first == entity { *second } else { *first }))
+            .count() as u32;
+
+        climber.ladders = ladders;
+        if !climber.on_ladder() {
+            climber.climbing = false;
+        }
+    }
+}
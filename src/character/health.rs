@@ -0,0 +1,239 @@
+use std::time::Duration;
+
+use bevy::{
+    app::{
+        Plugin,
+        PreStartup,
+        Update
+    },
+    ecs::{
+        component::ComponentId,
+        world::DeferredWorld
+    },
+    math::Vec2,
+    prelude::{
+        Commands,
+        Component,
+        Entity,
+        Event,
+        IntoSystemConfigs,
+        Query,
+        Res,
+        Resource,
+        Trigger,
+        Visibility,
+        With,
+        World
+    },
+    reflect::Reflect,
+    time::{
+        Time,
+        Timer,
+        TimerMode
+    }
+};
+use bevy_rapier2d::prelude::{CollisionEvent, Velocity};
+
+use crate::game_flow::Gameplay;
+
+use super::{CharacterColision, Dead, PlayerTag};
+
+pub struct HealthPlugin;
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .add_systems(PreStartup, |world: &mut World| {
+                world.register_component_hooks::<Health>().on_add(announce_health);
+            })
+            .insert_resource(DamageConfig::default())
+            .register_type::<DamageConfig>()
+            .register_type::<Health>()
+            .observe(apply_damage)
+            .observe(hurt_on_hazard_contact)
+            .observe(hurt_on_hazard_overlap)
+            .add_systems(Update, (tick_invincibility, tick_knockback).in_set(Gameplay));
+    }
+}
+
+/// How long a character can't be hurt after being damaged and how its sprite flashes during that time.
+#[derive(Resource, Reflect, Debug, Clone)]
+pub struct DamageConfig {
+    pub invincibility: Duration,
+    pub flash_interval: Duration,
+    /// How long knockback overrides the movement input.
+    pub knockback: Duration,
+}
+
+impl Default for DamageConfig {
+    fn default() -> Self {
+        Self {
+            invincibility: Duration::from_millis(1500),
+            flash_interval: Duration::from_millis(100),
+            knockback: Duration::from_millis(200),
+        }
+    }
+}
+
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+pub struct Health {
+    current: u32,
+    max: u32,
+}
+
+impl Health {
+    pub fn new(max: u32) -> Self { Self { current: max, max } }
+    pub fn current(&self) -> u32 { self.current }
+    pub fn max(&self) -> u32 { self.max }
+    pub fn is_dead(&self) -> bool { self.current == 0 }
+}
+
+/// Trigger this on a character to hurt it.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Damage {
+    pub amount: u32,
+    /// Velocity the character is pushed away with.
+    pub knockback: Vec2,
+}
+
+/// Triggered on a character whenever its [Health] changes, observe it to react to health changes (a hud, sound effects, ...).
+#[derive(Event, Debug, Clone, Copy)]
+pub struct HealthChanged {
+    pub previous: u32,
+    pub current: u32,
+    pub max: u32,
+}
+
+#[derive(Component, Debug)]
+pub struct Invincible {
+    timer: Timer,
+    flash: Timer,
+}
+
+impl Invincible {
+    pub fn new(config: &DamageConfig) -> Self {
+        Self::lasting(config.invincibility, config)
+    }
+
+    pub fn lasting(duration: Duration, config: &DamageConfig) -> Self {
+        Self {
+            timer: Timer::new(duration, TimerMode::Once),
+            flash: Timer::new(config.flash_interval, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Overrides [DamageConfig::invincibility] for a character, enemies hit by several attacks in a row can't wait as long as the player.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct InvincibilityTime(pub Duration);
+
+/// Hurts the player when touched, like spikes.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Hazard {
+    pub damage: u32,
+}
+
+/// Movement input is ignored while a character is knocked back.
+#[derive(Component, Debug)]
+pub struct Knockback(Timer);
+
+pub fn set_health(commands: &mut Commands, entity: Entity, health: &mut Health, current: u32) {
+    let previous = health.current;
+    health.current = current.min(health.max);
+    if previous != health.current {
+        commands.trigger_targets(HealthChanged { previous, current: health.current, max: health.max }, entity);
+    }
+}
+
+/// Lets observers know the starting health of a character.
+fn announce_health(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
+    let Health { current, max } = *world.get::<Health>(entity).expect("This is a hook for on_add health!");
+    world.commands().trigger_targets(HealthChanged { previous: 0, current, max }, entity);
+}
+
+fn apply_damage(
+    trigger: Trigger<Damage>,
+    mut commands: Commands,
+    config: Res<DamageConfig>,
+    mut characters: Query<(&mut Health, Option<&mut Velocity>, Option<&Invincible>, Option<&InvincibilityTime>)>,
+) {
+    let entity = trigger.entity();
+    let Ok((mut health, velocity, invincible, invincibility)) = characters.get_mut(entity) else { return; };
+    if invincible.is_some() || health.is_dead() { return; }
+
+    let damage = trigger.event();
+    let current = health.current.saturating_sub(damage.amount);
+    set_health(&mut commands, entity, &mut health, current);
+
+    if health.is_dead() {
+        commands.entity(entity).insert(Dead);
+        return;
+    }
+
+    if let Some(mut velocity) = velocity {
+        velocity.linvel = damage.knockback;
+    }
+    let invincibility = invincibility.map_or(config.invincibility, |invincibility| invincibility.0);
+    commands.entity(entity).insert((
+        Invincible::lasting(invincibility, &config),
+        Knockback(Timer::new(config.knockback, TimerMode::Once)),
+    ));
+}
+
+const HAZARD_KNOCKBACK: Vec2 = Vec2::new(0.0, 150.0);
+
+/// Solid hazards are hit by the character controller.
+fn hurt_on_hazard_contact(
+    trigger: Trigger<CharacterColision>,
+    mut commands: Commands,
+    hazards: Query<&Hazard>,
+) {
+    let Ok(hazard) = hazards.get(trigger.entity()) else { return; };
+    commands.trigger_targets(Damage { amount: hazard.damage, knockback: HAZARD_KNOCKBACK }, trigger.event().character);
+}
+
+/// Sensor hazards only report that the player overlaps them.
+fn hurt_on_hazard_overlap(
+    trigger: Trigger<CollisionEvent>,
+    mut commands: Commands,
+    hazards: Query<&Hazard>,
+    player: Query<(), With<PlayerTag>>,
+) {
+    let Ok(hazard) = hazards.get(trigger.entity()) else { return; };
+    let CollisionEvent::Started(first, second, _) = *trigger.event() else { return; };
+    let other = if first == trigger.entity() { second } else { first };
+    if !player.contains(other) { return; }
+    commands.trigger_targets(Damage { amount: hazard.damage, knockback: HAZARD_KNOCKBACK }, other);
+}
+
+fn tick_invincibility(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut characters: Query<(Entity, &mut Invincible, &mut Visibility)>,
+) {
+    for (entity, mut invincible, mut visibility) in characters.iter_mut() {
+        if invincible.timer.tick(time.delta()).finished() {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<Invincible>();
+            continue;
+        }
+
+        if invincible.flash.tick(time.delta()).just_finished() {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
+        }
+    }
+}
+
+fn tick_knockback(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut characters: Query<(Entity, &mut Knockback)>,
+) {
+    for (entity, mut knockback) in characters.iter_mut() {
+        if knockback.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
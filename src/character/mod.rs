@@ -1,3 +1,8 @@
+mod climbing;
+mod health;
+mod respawn;
+mod attack;
+
 use std::{ops::Mul, time::Duration};
 
 use bevy::{
@@ -8,27 +13,32 @@ use bevy::{
         Update
     }, asset::{
         AssetServer, 
+        Assets, 
         Handle
-    }, color::Color, log::info, math::{Vec2, VectorSpace}, prelude::{
-        Bundle, Commands, Component, Entity, Event, GamepadButtonType, Image, KeyCode, Local, Query, Res, Resource
+    }, color::Color, log::info, math::{UVec2, Vec2, VectorSpace}, prelude::{
+        Bundle, Commands, Component, Entity, Event, GamepadButtonType, Has, Image, IntoSystemConfigs, KeyCode, Local, Query, Res, ResMut, Resource, Trigger, With
     }, reflect::Reflect, sprite::{
         Sprite, 
-        SpriteBundle
+        SpriteBundle, 
+        TextureAtlasLayout
     }, time::{
         Stopwatch, 
         Time
     }, utils::default
 };
-use bevy_ecs_ldtk::LdtkEntity;
+use bevy_ecs_ldtk::{EntityInstance, LdtkEntity, Worldly};
 use bevy_rapier2d::{
     plugin::RapierConfiguration, 
     prelude::{
         ActiveCollisionTypes, 
         ActiveEvents, 
         Collider, 
+        CollisionGroups, 
         GravityScale, 
+        Group, 
         KinematicCharacterController, 
         KinematicCharacterControllerOutput, 
+        QueryFilterFlags, 
         ShapeCastHit, 
         Velocity
     }
@@ -46,16 +56,26 @@ use leafwing_input_manager::{
     },
 };
 
-use crate::unsorted::{Promise, PromiseProcedure, BevyPromiseResolver};
+use crate::{collision::{DropThrough, LocalGroupNames, OneWayPlatform}, enemies::Stomped, game_flow::Gameplay, items::Inventory, unsorted::{entities, Promise, PromiseProcedure, BevyPromiseResolver}};
+
+pub use health::{set_health, Damage, Hazard, Health, HealthChanged, InvincibilityTime};
+pub use respawn::{Dead, Lives, RespawnPoint};
+use climbing::Climber;
+use health::Knockback;
 
 pub struct CharacterPlugin;
 impl Plugin for CharacterPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app
-            .register_ldtk_entity_with_promise::<Player>("Player")
+            .register_ldtk_entity_with_promise::<Player>(entities::PLAYER)
             .add_plugins(InputManagerPlugin::<CharacterAction>::default())
+            .add_plugins(respawn::RespawnPlugin)
+            .add_plugins(health::HealthPlugin)
+            .add_plugins(climbing::ClimbingPlugin)
+            .add_plugins(attack::AttackPlugin)
             .add_systems(PreStartup, load_sprites)
-            .add_systems(Update, (player_movement, character_colision))
+            .observe(bounce_on_stomp)
+            .add_systems(Update, (player_movement, face_movement_direction, character_colision, interact).in_set(Gameplay))
             ;
     }
 }
@@ -65,15 +85,49 @@ impl Plugin for CharacterPlugin {
 #[derive(Actionlike, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 enum CharacterAction {
     #[actionlike(Axis)]   Move,
+    #[actionlike(Axis)]   Climb,
     #[actionlike(Button)] Jump,
+    #[actionlike(Button)] Interact,
+    #[actionlike(Button)] Attack,
+}
+
+/// Triggered when a character presses interact, observe it to let the character open or use things.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Interact {
+    pub character: Entity,
 }
 
 
 #[derive(LdtkEntity, Bundle, Default)]
 struct Player {
-    promise: Promise<Self>
+    promise: Promise<Self>,
     // #[sprite_sheet_bundle]
     // sprite_sheet_bundle: LdtkSpriteSheetBundle,
+
+    // the player isn't part of a level, so it isn't despawned when the level it started in is unloaded
+    #[worldly]
+    worldly: Worldly,
+
+    #[with(retrieve_items)]
+    inventory: Inventory,
+}
+
+fn retrieve_items(entity_instance: &EntityInstance) -> Inventory {
+    Inventory::from_ldtk_field(entity_instance, "items")
+}
+
+#[derive(Component, Clone, Copy)]
+pub struct PlayerTag;
+
+/// Multiplies the movement and jump velocity of a character, given by items like boots.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct MovementBoost {
+    pub speed: f32,
+    pub jump: f32,
+}
+
+impl Default for MovementBoost {
+    fn default() -> Self { Self { speed: 1.0, jump: 1.0 } }
 }
 
 fn standard_character_input_map() -> InputManagerBundle<CharacterAction> {
@@ -83,8 +137,17 @@ fn standard_character_input_map() -> InputManagerBundle<CharacterAction> {
             .with_axis(CharacterAction::Move, KeyboardVirtualAxis::AD)
             .with_axis(CharacterAction::Move, GamepadControlAxis::LEFT_X.with_deadzone_symmetric(0.1))
 
+            .with_axis(CharacterAction::Climb, KeyboardVirtualAxis::WS)
+            .with_axis(CharacterAction::Climb, GamepadControlAxis::LEFT_Y.with_deadzone_symmetric(0.1))
+
             .with(CharacterAction::Jump, KeyCode::Space)
             .with(CharacterAction::Jump, GamepadButtonType::South)
+
+            .with(CharacterAction::Interact, KeyCode::KeyE)
+            .with(CharacterAction::Interact, GamepadButtonType::West)
+
+            .with(CharacterAction::Attack, KeyCode::KeyF)
+            .with(CharacterAction::Attack, GamepadButtonType::East)
     )
 }
 
@@ -95,9 +158,19 @@ impl PromiseProcedure for Player {
             .commands()
             .entity(entity)
             .insert((
+                PlayerTag,
+                Health::new(PLAYER_HEALTH),
+                Climber::default(),
                 standard_character_input_map(),
-                KinematicCharacterController::default(),
+                KinematicCharacterController {
+                    // sensors (checkpoints, pickups, ...) shouldn't block the character
+                    filter_flags: QueryFilterFlags::EXCLUDE_SENSORS,
+                    // pickups are collected, not walked on, one-way platforms are only solid while they are `PLAYER_SOLID`
+                    filter_groups: Some(CollisionGroups::new(Group::PLAYER, Group::ALL & !(Group::PICKUP | Group::ONE_WAY_PLATFORM))),
+                    ..default()
+                },
                 Collider::capsule_y(4., 4.),
+                CollisionGroups::new(Group::PLAYER, Group::ALL),
                 ActiveEvents::COLLISION_EVENTS,
                 ActiveCollisionTypes::all(),
                 Velocity::default(),
@@ -116,35 +189,96 @@ impl PromiseProcedure for Player {
     }
 }
 
+const PLAYER_HEALTH: u32 = 3;
+
 const MOVEMENT_VELOCITY: f32 = 200.0;
 const JUMP_VELOCITY: f32 = 100.0;
 const GRAVITY_INFLUENCE_JUMP: f32 = 0.3;
 const COYOTE_TIME: Duration = Duration::from_millis(100);
+const CLIMB_VELOCITY: f32 = 60.0;
+const STOMP_BOUNCE_VELOCITY: f32 = 80.0;
+/// Bounce velocity when jump is held while stomping.
+const STOMP_BOUNCE_VELOCITY_JUMP: f32 = 130.0;
+
+/// The character bounces off an enemy it stomped the next time it moves.
+#[derive(Component, Debug)]
+struct StompBounce;
+
+fn bounce_on_stomp(trigger: Trigger<Stomped>, mut commands: Commands) {
+    commands.entity(trigger.entity()).insert(StompBounce);
+}
 
 fn player_movement(
+    mut commands: Commands,
     time: Res<Time>,
     physics: Res<RapierConfiguration>,
     mut jumped: Local<bool>,
     mut since_last_grounded: Local<Stopwatch>,
+    platforms: Query<(), With<OneWayPlatform>>,
     mut query: Query<(
+        Entity,
         &ActionState<CharacterAction>, 
         &mut KinematicCharacterController,
         Option<&KinematicCharacterControllerOutput>, 
         &mut Velocity, 
-        &GravityScale
+        &GravityScale,
+        Option<&Knockback>,
+        Option<&MovementBoost>,
+        Option<&mut Climber>,
+        Has<StompBounce>
     )>,
 ) {
 
     for 
     (
+        entity,
         input, 
         mut character_controller, 
         character_output, 
         mut velocity, 
-        gravity_scale
+        gravity_scale,
+        knockback,
+        boost,
+        climber,
+        stomped
     ) 
     in query.iter_mut() 
     {
+        let boost = boost.copied().unwrap_or_default();
+
+        let input_axis_horizontal = input
+            .axis_data(&CharacterAction::Move)
+            .map(|axis| axis.value)
+            .unwrap_or_default();
+        let input_axis_vertical = input
+            .axis_data(&CharacterAction::Climb)
+            .map(|axis| axis.value)
+            .unwrap_or_default();
+
+        if let Some(mut climber) = climber {
+            // holding jump keeps the character from grabbing the ladder it just jumped off
+            if climber.on_ladder() && input_axis_vertical != 0.0 && !input.pressed(&CharacterAction::Jump) {
+                climber.climbing = true;
+            }
+
+            if climber.climbing {
+                if input.just_pressed(&CharacterAction::Jump) {
+                    // jumping while holding down drops off the ladder
+                    climber.climbing = false;
+                    if input_axis_vertical >= 0.0 {
+                        velocity.linvel.y = JUMP_VELOCITY * boost.jump;
+                        *jumped = true;
+                    }
+                }
+                else {
+                    // no gravity while climbing
+                    velocity.linvel = Vec2::new(input_axis_horizontal, input_axis_vertical) * CLIMB_VELOCITY;
+                    character_controller.translation = Some(velocity.linvel * time.delta_seconds());
+                    continue;
+                }
+            }
+        }
+
         let mut gravity_scale = gravity_scale.0;
         let mut grounded = character_output
             .map(|output| output.grounded)
@@ -158,10 +292,25 @@ fn player_movement(
             grounded = since_last_grounded.elapsed() < COYOTE_TIME;  
         }
 
+        // down and jump on a one-way platform drops through it instead of jumping
+        let on_platform = character_output
+            .is_some_and(|output| output.collisions.iter().any(|collision| platforms.contains(collision.entity)));
+        if on_platform && input_axis_vertical < 0.0 && input.just_pressed(&CharacterAction::Jump) {
+            commands.entity(entity).insert(DropThrough::default());
+            *jumped = true;
+        }
+
+        // a bounce counts as a jump, so it isn't cancelled while the character still counts as grounded
+        if stomped {
+            commands.entity(entity).remove::<StompBounce>();
+            velocity.linvel.y = if input.pressed(&CharacterAction::Jump) { STOMP_BOUNCE_VELOCITY_JUMP } else { STOMP_BOUNCE_VELOCITY };
+            *jumped = true;
+        }
+
         if input.pressed(&CharacterAction::Jump) {
             gravity_scale *= GRAVITY_INFLUENCE_JUMP;
             if grounded && !(*jumped) { 
-                velocity.linvel.y = JUMP_VELOCITY; 
+                velocity.linvel.y = JUMP_VELOCITY * boost.jump; 
                 *jumped = true;
             }
         }
@@ -169,16 +318,15 @@ fn player_movement(
         let gravity = physics.gravity * gravity_scale;
         let acceleration = gravity;
         velocity.linvel += acceleration * time.delta_seconds();
-        
-        let input_axis_horizontal = input
-            .axis_data(&CharacterAction::Move)
-            .map(|axis| axis.value)
-            .unwrap_or_default();
 
-        velocity.linvel.x = input_axis_horizontal * MOVEMENT_VELOCITY;        
+        // knockback overrides the movement until it wears off
+        let knocked_back = knockback.is_some();
+        if !knocked_back {
+            velocity.linvel.x = input_axis_horizontal * MOVEMENT_VELOCITY * boost.speed;        
+        }
                
         if grounded {
-            if !(*jumped) {
+            if !(*jumped) && !knocked_back {
                 velocity.linvel.y = 0.0;
             }
         }
@@ -231,7 +379,7 @@ impl JumpTracker {
     }
 }
 
-#[derive(Event, Debug)]
+#[derive(Event, Debug, Clone, Copy)]
 pub struct CharacterColision{
     pub hit: ShapeCastHit,
     pub character: Entity
@@ -248,17 +396,61 @@ fn character_colision(
     }
 }
 
+/// The player's sprite faces right, it is flipped while moving left so attacks go the way the player looks.
+fn face_movement_direction(mut query: Query<(&ActionState<CharacterAction>, &mut Sprite)>) {
+    for (input, mut sprite) in query.iter_mut() {
+        let input_axis_horizontal = input
+            .axis_data(&CharacterAction::Move)
+            .map(|axis| axis.value)
+            .unwrap_or_default();
+        if input_axis_horizontal == 0.0 { continue; }
+
+        let flip_x = input_axis_horizontal < 0.0;
+        if sprite.flip_x != flip_x {
+            sprite.flip_x = flip_x;
+        }
+    }
+}
+
+fn interact(
+    mut commands: Commands,
+    query: Query<(Entity, &ActionState<CharacterAction>)>,
+) {
+    for (character, input) in query.iter() {
+        if input.just_pressed(&CharacterAction::Interact) {
+            commands.trigger(Interact { character });
+        }
+    }
+}
+
 #[derive(Resource)]
 pub(crate) struct ImageHandles {
     pub player: Handle<Image>,
     pub test_enemy: Handle<Image>,
+    pub atlas: Handle<Image>,
+    pub atlas_layout: Handle<TextureAtlasLayout>,
 }
 
+impl ImageHandles {
+    pub fn is_loaded(&self, server: &AssetServer) -> bool {
+        server.is_loaded_with_dependencies(&self.player) 
+        && server.is_loaded_with_dependencies(&self.test_enemy)
+        && server.is_loaded_with_dependencies(&self.atlas)
+    }
+}
+
+const ATLAS_TILE_SIZE: u32 = 16;
+const ATLAS_COLUMNS: u32 = 23;
+const ATLAS_ROWS: u32 = 21;
+
 fn load_sprites(
     mut commands: Commands,
-    server: Res<AssetServer>
+    server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
     let player = server.load("Character.png");
     let test_enemy = server.load("Enemy.png");
-    commands.insert_resource(ImageHandles { player, test_enemy });
+    let atlas = server.load("atlas/SunnyLand_by_Ansimuz-extended.png");
+    let atlas_layout = layouts.add(TextureAtlasLayout::from_grid(UVec2::splat(ATLAS_TILE_SIZE), ATLAS_COLUMNS, ATLAS_ROWS, None, None));
+    commands.insert_resource(ImageHandles { player, test_enemy, atlas, atlas_layout });
 }
\ No newline at end of file
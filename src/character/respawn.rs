@@ -0,0 +1,138 @@
+use bevy::{
+    app::{
+        Plugin,
+        Update
+    },
+    math::Vec2,
+    prelude::{
+        Commands,
+        Component,
+        Entity,
+        IntoSystemConfigs,
+        NextState,
+        OnEnter,
+        OnExit,
+        Query,
+        Res,
+        ResMut,
+        Resource,
+        Transform,
+        Trigger,
+        With
+    }
+};
+use bevy_ecs_ldtk::{LevelIid, Respawn};
+use bevy_rapier2d::prelude::Velocity;
+
+use crate::game_flow::{GameState, Gameplay, LevelCleared, LevelProgression};
+
+use super::{health::{set_health, DamageConfig, Health, Invincible}, PlayerTag};
+
+const STARTING_LIVES: u32 = 3;
+
+pub struct RespawnPlugin;
+impl Plugin for RespawnPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .init_resource::<RespawnPoint>()
+            // lives carry over between levels, a new run starts whenever a level is started from the main menu
+            .add_systems(OnExit(GameState::MainMenu), reset_lives)
+            // retrying a level after running out of lives gives a new set of lives
+            .add_systems(OnEnter(GameState::Defeated), reset_lives)
+            // retrying a level starts it from the beginning
+            .add_systems(OnEnter(GameState::Playing), reset_respawn_point)
+            .add_systems(Update, respawn_dead_player.in_set(Gameplay))
+            .observe(|_trigger: Trigger<LevelCleared>, mut respawn_point: ResMut<RespawnPoint>| respawn_point.clear());
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct Lives(u32);
+
+impl Lives {
+    pub fn remaining(&self) -> u32 { self.0 }
+}
+
+/// Where the player respawns after dying, the start of the level when no checkpoint is reached yet.
+#[derive(Resource, Debug, Default)]
+pub struct RespawnPoint(Option<Vec2>);
+
+impl RespawnPoint {
+    pub fn set(&mut self, position: Vec2) { self.0 = Some(position); }
+    pub fn clear(&mut self) { self.0 = None; }
+}
+
+/// Marks a character that died this frame, it is handled once even when it is killed multiple times.
+#[derive(Component, Debug)]
+pub struct Dead;
+
+fn reset_lives(mut commands: Commands) {
+    commands.insert_resource(Lives(STARTING_LIVES));
+}
+
+fn reset_respawn_point(mut respawn_point: ResMut<RespawnPoint>) {
+    respawn_point.clear();
+}
+
+fn respawn_dead_player(
+    mut commands: Commands,
+    mut player: Query<(Entity, &mut Transform, &mut Velocity, &mut Health), (With<Dead>, With<PlayerTag>)>,
+    levels: Query<(Entity, &LevelIid)>,
+    mut lives: ResMut<Lives>,
+    respawn_point: Res<RespawnPoint>,
+    mut progression: ResMut<LevelProgression>,
+    mut next_state: ResMut<NextState<GameState>>,
+    damage_config: Res<DamageConfig>,
+) {
+    let Ok((entity, mut transform, mut velocity, mut health)) = player.get_single_mut() else { return; };
+    commands.entity(entity).remove::<Dead>();
+
+    lives.0 = lives.0.saturating_sub(1);
+    if lives.0 == 0 {
+        next_state.set(GameState::Defeated);
+        return;
+    }
+
+    let max_health = health.max();
+    set_health(&mut commands, entity, &mut health, max_health);
+    commands.entity(entity).insert(Invincible::new(&damage_config));
+
+    velocity.linvel = Vec2::ZERO;
+    match respawn_point.0 {
+        Some(position) => transform.translation = position.extend(transform.translation.z),
+        None => progression.restart_level(),
+    }
+
+    // enemies are reset by respawning the level the player is in, the player is worldly so it isn't respawned
+    let current = progression.current_level_iid();
+    for (level, iid) in levels.iter() {
+        if Some(iid.as_str()) == current {
+            commands.entity(level).insert(Respawn);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{app::App, state::app::{AppExtStates, StatesPlugin}};
+
+    use super::*;
+
+    #[test]
+    fn retrying_after_a_game_over_restores_the_lives() {
+        let mut app = App::new();
+        app
+            .add_plugins((StatesPlugin, RespawnPlugin))
+            .init_state::<GameState>()
+            .init_resource::<LevelProgression>()
+            .init_resource::<DamageConfig>()
+            .insert_resource(Lives(0));
+
+        app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Defeated);
+        app.update();
+        app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Playing);
+        app.update();
+
+        assert_eq!(app.world().resource::<Lives>().remaining(), STARTING_LIVES);
+    }
+}
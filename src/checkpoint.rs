@@ -0,0 +1,57 @@
+use bevy::{app::Plugin, ecs::{component::ComponentId, world::DeferredWorld}, prelude::{BuildChildren, Bundle, Entity, GlobalTransform, Query, ResMut, SpatialBundle, Trigger, With}};
+use bevy_ecs_ldtk::LdtkEntity;
+use bevy_rapier2d::prelude::{Collider, CollisionEvent, CollisionGroups, Group};
+
+use crate::{character::{PlayerTag, RespawnPoint}, collision::LocalGroupNames, enemies::{ColliderBundle, ObservableColliderBundle}, unsorted::{entities, BevyPromiseResolver, Promise, PromiseProcedure}};
+
+
+#[derive(Default, Bundle, LdtkEntity)]
+struct Checkpoint {
+    promise: Promise<Self>,
+}
+
+pub struct CheckpointPlugin;
+impl Plugin for CheckpointPlugin
+{
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.register_ldtk_entity_with_promise::<Checkpoint>(entities::CHECKPOINT);
+    }
+}
+
+const TILESIZE: f32 = 16.0;
+
+impl PromiseProcedure for Checkpoint {
+    fn resolve_promise(mut world: DeferredWorld, entity: Entity, component_id: ComponentId) {
+        world
+            .commands()
+            .entity(entity)
+            .with_children(|children| {
+                children.spawn((
+                    SpatialBundle::default(),
+                    ObservableColliderBundle::from(ColliderBundle {
+                        collider: Collider::cuboid(TILESIZE / 2.0, TILESIZE / 2.0),
+                        collision_groups: CollisionGroups {
+                            memberships: Group::CHECKPOINT,
+                            filters: Group::PLAYER,
+                        },
+                    }),
+                ))
+                .observe(record_respawn_point);
+            })
+            .remove_by_id(component_id);
+    }
+}
+
+fn record_respawn_point(
+    trigger: Trigger<CollisionEvent>,
+    checkpoints: Query<&GlobalTransform>,
+    player: Query<(), With<PlayerTag>>,
+    mut respawn_point: ResMut<RespawnPoint>,
+) {
+    let CollisionEvent::Started(first, second, _) = *trigger.event() else { return; };
+    let other = if first == trigger.entity() { second } else { first };
+    if !player.contains(other) { return; }
+
+    let Ok(checkpoint) = checkpoints.get(trigger.entity()) else { return; };
+    respawn_point.set(checkpoint.translation().truncate());
+}
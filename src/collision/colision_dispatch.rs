@@ -0,0 +1,53 @@
+use std::{marker::PhantomData, ops::Deref};
+
+use bevy::{
+    app::App,
+    ecs::system::IntoObserverSystem,
+    prelude::{Commands, Component, Event, Query, Trigger, With}
+};
+
+use crate::character::CharacterColision;
+
+/// Routes the [CharacterColision]s with every entity of a kind to one handler.
+///
+/// A single global observer per kind passes the colisions with entities that have the component `K` on to the handler as [ColisionWith].
+/// Entities don't need an observer of their own, so nothing has to change when they spawn or despawn.
+pub trait CharacterColisionDispatch {
+    fn dispatch_character_colisions<K: Component, M>(
+        &mut self,
+        handler: impl IntoObserverSystem<ColisionWith<K>, (), M>,
+    ) -> &mut Self;
+}
+
+impl CharacterColisionDispatch for App {
+    fn dispatch_character_colisions<K: Component, M>(
+        &mut self,
+        handler: impl IntoObserverSystem<ColisionWith<K>, (), M>,
+    ) -> &mut Self {
+        self
+            .observe(route::<K>)
+            .observe(handler)
+    }
+}
+
+/// A [CharacterColision] with an entity of the kind `K`, triggered on that entity.
+#[derive(Event, Debug)]
+pub struct ColisionWith<K: Component> {
+    colision: CharacterColision,
+    kind: PhantomData<fn() -> K>,
+}
+
+impl<K: Component> Deref for ColisionWith<K> {
+    type Target = CharacterColision;
+    fn deref(&self) -> &Self::Target { &self.colision }
+}
+
+fn route<K: Component>(
+    trigger: Trigger<CharacterColision>,
+    mut commands: Commands,
+    kind: Query<(), With<K>>,
+) {
+    if !kind.contains(trigger.entity()) { return; }
+    let colision = ColisionWith::<K> { colision: *trigger.event(), kind: PhantomData };
+    commands.trigger_targets(colision, trigger.entity());
+}
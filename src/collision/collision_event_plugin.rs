@@ -1,13 +1,20 @@
-use bevy::{app::{App, Plugin}, log::info, prelude::Trigger};
+use bevy::{app::{App, Plugin, Update}, prelude::{Commands, EventReader}};
 use bevy_rapier2d::prelude::CollisionEvent;
 
+/// Forwards rapier's [CollisionEvent]s as triggers to both entities involved, so they can be [observed](bevy::prelude::Observer).
 pub struct CollisionHanlerPlugin;
 impl Plugin for CollisionHanlerPlugin {
     fn build(&self, app: &mut App) {
-        app.observe(collision_handler);
+        app.add_systems(Update, trigger_collision_events);
     }
 }
 
-fn collision_handler(trigger: Trigger<CollisionEvent>) {
-    info!("collided entity: {}", trigger.entity());
-}
\ No newline at end of file
+fn trigger_collision_events(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+) {
+    for event in collision_events.read() {
+        let (CollisionEvent::Started(first, second, _) | CollisionEvent::Stopped(first, second, _)) = *event;
+        commands.trigger_targets(*event, [first, second]);
+    }
+}
@@ -3,9 +3,20 @@ use bevy_rapier2d::geometry::Group;
 impl LocalGroupNames for Group {}
 pub trait LocalGroupNames {
     const PLAYER: Group = Group::GROUP_1;
+    const PLAYER_ATTACK: Group = Group::GROUP_2;
 
     const TEST_ENEMY: Group = Group::GROUP_11;
     const TEST_ENEMY_SENSOR: Group = Group::GROUP_12;
+    const PROJECTILE: Group = Group::GROUP_13;
+
+    const CHECKPOINT: Group = Group::GROUP_20;
+    const PICKUP: Group = Group::GROUP_21;
+    const CHEST: Group = Group::GROUP_22;
+    const DOOR: Group = Group::GROUP_23;
+    const LADDER: Group = Group::GROUP_24;
+    const ONE_WAY_PLATFORM: Group = Group::GROUP_25;
+    /// Added to a one-way platform while the player stands on it.
+    const PLAYER_SOLID: Group = Group::GROUP_26;
 
     // const ENTITIES: Group;
     // const SENSORS: Group;
@@ -0,0 +1,189 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::Assets,
+    math::{URect, UVec2, Vec2},
+    prelude::{BuildChildren, Commands, Component, Entity, EventReader, Query, Res, SpatialBundle, Transform}
+};
+use bevy_ecs_ldtk::{assets::LdtkProject, ldtk::{Level, TilesetDefinition}, LevelEvent, LevelIid};
+use bevy_rapier2d::prelude::Collider;
+
+use crate::unsorted::{enums::{self, EnumId}, int_grid::collisions, ldtk_level_handler::WorldHandle};
+
+/// `dirt` and `stone` cells are solid, `ladder` cells aren't.
+const SOLID_INT_CELLS: [i32; 2] = [collisions::DIRT, collisions::STONE];
+
+/// Instead of a collider per solid cell, the solid cells of a level are merged into a few large rectangles.
+/// This keeps the amount of colliders low and stops characters from snagging on the edges between cells.
+///
+/// The colliders are children of the level, so they are rebuilt whenever the level is (re)spawned.
+pub struct LevelColliderPlugin;
+impl Plugin for LevelColliderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, spawn_level_colliders);
+    }
+}
+
+#[derive(Component, Debug)]
+struct LevelCollider;
+
+fn spawn_level_colliders(
+    mut commands: Commands,
+    mut level_events: EventReader<LevelEvent>,
+    levels: Query<(Entity, &LevelIid)>,
+    world: Res<WorldHandle>,
+    projects: Res<Assets<LdtkProject>>,
+) {
+    for event in level_events.read() {
+        let LevelEvent::Spawned(iid) = event else { continue; };
+        let Some((level_entity, _)) = levels.iter().find(|(_, level_iid)| *level_iid == iid) else { continue; };
+        let Some(project) = projects.get(&world.handle()) else { continue; };
+        let Some(level) = project.json_data().levels.iter().find(|level| level.iid == *iid.get()) else { continue; };
+
+        let grid_size = project.json_data().default_grid_size;
+        let solid = SolidCells::from_level(level, &project.json_data().defs.tilesets, grid_size);
+        commands.entity(level_entity).with_children(|children| {
+            for rect in solid.merge() {
+                let size = (rect.size() * grid_size as u32).as_vec2();
+                // LDtk counts the cells from the top, the level from the bottom
+                let top_left = (rect.min * grid_size as u32).as_vec2();
+                let center = Vec2::new(top_left.x + size.x / 2.0, level.px_hei as f32 - top_left.y - size.y / 2.0);
+                children.spawn((
+                    LevelCollider,
+                    SpatialBundle::from_transform(Transform::from_translation(center.extend(0.0))),
+                    Collider::cuboid(size.x / 2.0, size.y / 2.0),
+                ));
+            }
+        });
+    }
+}
+
+struct SolidCells {
+    size: UVec2,
+    cells: Vec<bool>,
+}
+
+impl SolidCells {
+    /// Solid cells from the `Collisions` IntGrid layer and the tiles tagged `Solid` in any layer.
+    fn from_level(level: &Level, tilesets: &[TilesetDefinition], grid_size: i32) -> Self {
+        let size = UVec2::new((level.px_wid / grid_size) as u32, (level.px_hei / grid_size) as u32);
+        let mut solid = Self { size, cells: vec![false; (size.x * size.y) as usize] };
+
+        for layer in level.layer_instances.iter().flatten() {
+            if layer.identifier == collisions::IDENTIFIER {
+                for (index, value) in layer.int_grid_csv.iter().enumerate() {
+                    if SOLID_INT_CELLS.contains(value) {
+                        let index = index as i32;
+                        solid.set(index % layer.c_wid, index / layer.c_wid);
+                    }
+                }
+            }
+
+            let Some(tileset) = layer.tileset_def_uid.and_then(|uid| tilesets.iter().find(|tileset| tileset.uid == uid)) else { continue; };
+            if tileset.tags_source_enum_uid != Some(enums::Collider::ID) { continue; }
+            let Some(solid_tiles) = tileset.enum_tags.iter().find(|tag| tag.enum_value_id == enums::Collider::Solid.identifier()) else { continue; };
+            for tile in layer.grid_tiles.iter().chain(layer.auto_layer_tiles.iter()) {
+                if solid_tiles.tile_ids.contains(&tile.t) {
+                    solid.set(tile.px.x / layer.grid_size, tile.px.y / layer.grid_size);
+                }
+            }
+        }
+        solid
+    }
+
+    fn set(&mut self, x: i32, y: i32) {
+        if x < 0 || y < 0 || x as u32 >= self.size.x || y as u32 >= self.size.y { return; }
+        let index = self.index(x as u32, y as u32);
+        self.cells[index] = true;
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize { (y * self.size.x + x) as usize }
+
+    /// Greedily covers the solid cells with rectangles: a row of cells is grown as far as possible to the right,
+    /// then downwards while the row below is solid as well.
+    fn merge(mut self) -> Vec<URect> {
+        let mut rects = Vec::new();
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
+                if !self.cells[self.index(x, y)] { continue; }
+
+                let mut width = 1;
+                while x + width < self.size.x && self.cells[self.index(x + width, y)] { width += 1; }
+
+                let mut height = 1;
+                while y + height < self.size.y && (x..x + width).all(|cell| self.cells[self.index(cell, y + height)]) { height += 1; }
+
+                for row in y..y + height {
+                    for cell in x..x + width {
+                        let index = self.index(cell, row);
+                        self.cells[index] = false;
+                    }
+                }
+                rects.push(URect::new(x, y, x + width, y + height));
+            }
+        }
+        rects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `#` is a solid cell, anything else is empty.
+    fn cells(rows: &[&str]) -> SolidCells {
+        let mut solid = SolidCells { size: UVec2::new(rows[0].len() as u32, rows.len() as u32), cells: vec![false; rows.len() * rows[0].len()] };
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                if cell == '#' { solid.set(x as i32, y as i32); }
+            }
+        }
+        solid
+    }
+
+    /// Every solid cell is covered by exactly one rectangle and no empty cell is covered.
+    fn assert_covers(rows: &[&str], rects: &[URect]) {
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                let point = UVec2::new(x as u32, y as u32);
+                let covering = rects.iter().filter(|rect| point.cmpge(rect.min).all() && point.cmplt(rect.max).all()).count();
+                assert_eq!(covering, usize::from(cell == '#'), "cell {} covered {} times", point, covering);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_level_has_no_colliders() {
+        assert!(cells(&["...", "..."]).merge().is_empty());
+    }
+
+    #[test]
+    fn block_becomes_one_rect() {
+        let rows = ["....", ".###", ".###"];
+        let rects = cells(&rows).merge();
+        assert_eq!(rects, [URect::new(1, 1, 4, 3)]);
+    }
+
+    #[test]
+    fn rows_grow_down_only_while_the_row_below_is_as_wide() {
+        let rows = ["###", "##.", "##."];
+        let rects = cells(&rows).merge();
+        assert_eq!(rects, [URect::new(0, 0, 3, 1), URect::new(0, 1, 2, 3)]);
+        assert_covers(&rows, &rects);
+    }
+
+    #[test]
+    fn scattered_cells_are_covered_exactly_once() {
+        let rows = ["#.#.##", "###..#", ".#.###", "##..#."];
+        let rects = cells(&rows).merge();
+        assert_covers(&rows, &rects);
+    }
+
+    #[test]
+    fn cells_outside_of_the_level_are_ignored() {
+        let mut solid = cells(&["..", ".."]);
+        solid.set(-1, 0);
+        solid.set(2, 1);
+        solid.set(0, 2);
+        assert!(solid.merge().is_empty());
+    }
+}
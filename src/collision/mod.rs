@@ -1,6 +1,11 @@
 mod collision_groups;
 mod collision_event_plugin;
+mod colision_dispatch;
+mod level_colliders;
+mod one_way_platforms;
 
-#[allow(dead_code)]
 pub use collision_event_plugin::CollisionHanlerPlugin;
-pub use collision_groups::LocalGroupNames;
\ No newline at end of file
+pub use colision_dispatch::{CharacterColisionDispatch, ColisionWith};
+pub use collision_groups::LocalGroupNames;
+pub use level_colliders::LevelColliderPlugin;
+pub use one_way_platforms::{DropThrough, OneWayPlatform, OneWayPlatformPlugin};
\ No newline at end of file
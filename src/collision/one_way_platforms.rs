@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Plugin, Update},
+    prelude::{Commands, Component, Entity, GlobalTransform, Has, IntoSystemConfigs, Query, Res, With},
+    time::{Time, Timer, TimerMode}
+};
+use bevy_rapier2d::prelude::{Collider, CollisionGroups, Group};
+
+use crate::{character::PlayerTag, game_flow::Gameplay};
+
+use super::LocalGroupNames;
+
+/// How far the feet of the player may sink into a platform while still standing on it.
+const TOLERANCE: f32 = 2.0;
+const DROP_THROUGH_TIME: Duration = Duration::from_millis(250);
+
+/// Platforms the player can jump through from below and stand on from above.
+///
+/// The player's character controller ignores [Group::ONE_WAY_PLATFORM], so a platform only blocks the player while it is
+/// also in [Group::PLAYER_SOLID], which it is while the player is above it and isn't dropping through.
+/// Everything else always sees the platform as part of the level.
+pub struct OneWayPlatformPlugin;
+impl Plugin for OneWayPlatformPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (tick_drop_through, update_one_way_platforms).chain().in_set(Gameplay));
+    }
+}
+
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct OneWayPlatform;
+
+/// Lets a character fall through the one-way platform it is standing on.
+#[derive(Component, Debug)]
+pub struct DropThrough(Timer);
+
+impl Default for DropThrough {
+    fn default() -> Self { Self(Timer::new(DROP_THROUGH_TIME, TimerMode::Once)) }
+}
+
+fn tick_drop_through(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut characters: Query<(Entity, &mut DropThrough)>,
+) {
+    for (entity, mut drop_through) in characters.iter_mut() {
+        if drop_through.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<DropThrough>();
+        }
+    }
+}
+
+fn update_one_way_platforms(
+    player: Query<(&GlobalTransform, &Collider, Has<DropThrough>), With<PlayerTag>>,
+    mut platforms: Query<(&GlobalTransform, &Collider, &mut CollisionGroups), With<OneWayPlatform>>,
+) {
+    let Ok((player, player_collider, dropping)) = player.get_single() else { return; };
+    let feet = player.translation().y + player_collider.raw.compute_local_aabb().mins.y;
+
+    for (platform, platform_collider, mut collision_groups) in platforms.iter_mut() {
+        let top = platform.translation().y + platform_collider.raw.compute_local_aabb().maxs.y;
+        let solid = !dropping && feet >= top - TOLERANCE;
+        let memberships = if solid { Group::ONE_WAY_PLATFORM | Group::PLAYER_SOLID } else { Group::ONE_WAY_PLATFORM };
+        if collision_groups.memberships != memberships {
+            collision_groups.memberships = memberships;
+        }
+    }
+}
@@ -13,7 +13,7 @@ use bevy::{
 };
 
 #[derive(Resource)]
-pub struct Debug(bool);
+pub struct Debug(pub(super) bool);
 
 #[allow(dead_code)] 
 pub fn debug_active(debug: Res<Debug>) -> bool {debug.0}
@@ -2,6 +2,7 @@ mod debug_sets;
 mod debug_toggle;
 mod debug_movement;
 mod debug_camera;
+mod tile_diagnostics;
 
 
 // set structure
@@ -16,6 +17,7 @@ pub use debug_sets::{
 // default plugin definition
 use debug_sets::DebugSetsPlugin;
 use debug_toggle::DebugTogglePlugin;
+use tile_diagnostics::TileDiagnosticsOverlayPlugin;
 use bevy::{
     app::{
         Plugin, 
@@ -31,9 +33,9 @@ impl Plugin for DebugPlugin
         app
             // .add_systems(Startup, spawn_debug_camera)// make bundle
             .add_systems(Update, debug_movement::free_movement.in_set(Input))
-            .add_plugins((DebugSetsPlugin, DebugTogglePlugin));
+            .add_plugins((DebugSetsPlugin, DebugTogglePlugin, TileDiagnosticsOverlayPlugin));
 
         // replace the camera attached to the player and return to attached when toggled back
         // when toggle to debug start from current cameras position
     }
-}
\ No newline at end of file
+}
\ No newline at end of file
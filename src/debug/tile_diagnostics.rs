@@ -0,0 +1,83 @@
+use bevy::{
+    app::{
+        Plugin,
+        Startup,
+        Update
+    },
+    color::palettes::css::ORANGE_RED,
+    prelude::{
+        Commands,
+        Component,
+        DetectChanges,
+        Query,
+        Res,
+        TextBundle,
+        Visibility,
+        With
+    },
+    text::{Text, TextStyle},
+    ui::{PositionType, Style, Val, ZIndex},
+    utils::default
+};
+
+use crate::unsorted::TileDiagnostics;
+
+use super::debug_toggle::Debug;
+
+/// Lists the malformed tiles of the loaded levels while debugging.
+pub(super) struct TileDiagnosticsOverlayPlugin;
+impl Plugin for TileDiagnosticsOverlayPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .add_systems(Startup, spawn_overlay)
+            .add_systems(Update, (update_overlay_text, toggle_overlay));
+    }
+}
+
+#[derive(Component)]
+struct TileDiagnosticsOverlay;
+
+fn spawn_overlay(mut commands: Commands) {
+    commands.spawn((
+        TileDiagnosticsOverlay,
+        TextBundle {
+            text: Text::from_section("", TextStyle { font_size: 14.0, color: ORANGE_RED.into(), ..default() }),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(8.0),
+                left: Val::Px(8.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(100),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+    ));
+}
+
+fn update_overlay_text(
+    diagnostics: Res<TileDiagnostics>,
+    mut overlay: Query<&mut Text, With<TileDiagnosticsOverlay>>,
+) {
+    if !diagnostics.is_changed() { return; }
+    let Ok(mut text) = overlay.get_single_mut() else { return; };
+
+    let lines = diagnostics.errors
+        .iter()
+        .map(|error| format!("{} / {} ({}, {}): {}", error.level, error.layer, error.grid.x, error.grid.y, error.message))
+        .collect::<Vec<_>>();
+    text.sections[0].value = if lines.is_empty() {
+        String::new()
+    } else {
+        format!("Malformed tiles:\n{}", lines.join("\n"))
+    };
+}
+
+fn toggle_overlay(
+    debug: Res<Debug>,
+    mut overlay: Query<&mut Visibility, With<TileDiagnosticsOverlay>>,
+) {
+    if !debug.is_changed() { return; }
+    let Ok(mut visibility) = overlay.get_single_mut() else { return; };
+    *visibility = if debug.0 { Visibility::Inherited } else { Visibility::Hidden };
+}
@@ -0,0 +1,215 @@
+use std::time::Duration;
+
+use bevy::{
+    app::{Plugin, Update},
+    asset::{Assets, Handle},
+    color::Color,
+    ecs::{component::ComponentId, world::DeferredWorld},
+    math::Vec2,
+    prelude::{
+        BuildChildren, Bundle, Commands, Component, DespawnRecursiveExt, Entity, Event, Image, IntoSystemConfigs, NodeBundle,
+        Parent, Query, Res, SpatialBundle, StateScoped, TextBundle, Transform, Trigger, Visibility
+    },
+    sprite::Sprite,
+    text::TextStyle,
+    time::{Time, Timer, TimerMode},
+    ui::{JustifyContent, PositionType, Style, Val},
+    utils::default
+};
+use bevy_ecs_ldtk::{assets::LdtkProject, ldtk::ReferenceToAnEntityInstance, prelude::LdtkFields, EntityInstance, LdtkEntity};
+use bevy_rapier2d::{plugin::RapierContext, prelude::{Collider, CollisionGroups, Group, Velocity}};
+
+use crate::{
+    character::Interact,
+    collision::LocalGroupNames,
+    enemies::{ColliderBundle, ObservableColliderBundle},
+    font_handing::FontHandles,
+    game_flow::{GameState, Gameplay},
+    items::{Inventory, Item},
+    unsorted::{entities, ldtk_coords::entity_ref_world_center, ldtk_level_handler::WorldHandle, BevyPromiseResolver, Promise, PromiseProcedure}
+};
+
+
+#[derive(Default, Bundle, LdtkEntity)]
+struct Door {
+    promise: Promise<Self>,
+
+    #[with(retrieve_door_state)]
+    state: DoorState,
+}
+
+/// Set with the `locked` and `destination` fields in LDtk.
+/// Doors without a destination block the way until they are opened, doors with one teleport the player to the other door.
+#[derive(Component, Default, Clone, Debug)]
+struct DoorState {
+    locked: bool,
+    destination: Option<ReferenceToAnEntityInstance>,
+    /// Center of the destination door in bevy's world space, looked up when the door is spawned.
+    target: Option<Vec2>,
+    opened: bool,
+}
+
+fn retrieve_door_state(entity_instance: &EntityInstance) -> DoorState {
+    DoorState {
+        locked: entity_instance.get_bool_field("locked").copied().unwrap_or_default(),
+        destination: entity_instance.get_maybe_entity_ref_field("destination").ok().cloned().flatten(),
+        ..default()
+    }
+}
+
+pub struct DoorPlugin;
+impl Plugin for DoorPlugin
+{
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .register_ldtk_entity_with_promise::<Door>(entities::DOOR)
+            .add_systems(Update, hide_locked_message.in_set(Gameplay))
+            .observe(use_door)
+            .observe(show_locked_message);
+    }
+}
+
+const TILESIZE: f32 = 16.0;
+const WIDTH: f32 = 12.0;
+const LOCKED_COLOR: Color = Color::srgb(0.45, 0.2, 0.15);
+const UNLOCKED_COLOR: Color = Color::srgb(0.6, 0.4, 0.2);
+const LOCKED_MESSAGE_TIME: Duration = Duration::from_secs(2);
+
+impl PromiseProcedure for Door {
+    fn resolve_promise(mut world: DeferredWorld, entity: Entity, component_id: ComponentId) {
+        let height = world.get::<EntityInstance>(entity).map(|instance| instance.height as f32).unwrap_or(TILESIZE * 2.0);
+        let mut state = world.get::<DoorState>(entity).cloned().unwrap_or_default();
+        let project = world.resource::<Assets<LdtkProject>>().get(&world.resource::<WorldHandle>().handle());
+        state.target = state.destination.as_ref().zip(project).and_then(|(destination, project)| entity_ref_world_center(project.json_data(), destination));
+        let mut commands = world.commands();
+        let mut entity_commands = commands.entity(entity);
+
+        // only doors that don't lead anywhere are in the way
+        if state.destination.is_none() {
+            entity_commands.insert(Collider::cuboid(WIDTH / 2.0, height / 2.0));
+        }
+
+        entity_commands
+            .insert((
+                Sprite {
+                    color: if state.locked { LOCKED_COLOR } else { UNLOCKED_COLOR },
+                    custom_size: Some(Vec2::new(WIDTH, height)),
+                    ..default()
+                },
+                Handle::<Image>::default(),
+                state,
+            ))
+            .with_children(|children| {
+                children.spawn((
+                    SpatialBundle::default(),
+                    ObservableColliderBundle::from(ColliderBundle {
+                        // a bit wider than the door so the player can reach it while it blocks the way
+                        collider: Collider::cuboid(WIDTH / 2.0 + TILESIZE / 2.0, height / 2.0),
+                        collision_groups: CollisionGroups {
+                            memberships: Group::DOOR,
+                            filters: Group::PLAYER,
+                        },
+                    }),
+                ));
+            })
+            .remove_by_id(component_id);
+    }
+}
+
+/// Triggered when a character tries to open a locked door without a key.
+#[derive(Event, Debug)]
+struct LockedDoorUsed;
+
+#[derive(Component, Debug)]
+struct LockedMessage(Timer);
+
+/// Uses the door the interacting character stands at.
+fn use_door(
+    trigger: Trigger<Interact>,
+    mut commands: Commands,
+    rapier: Res<RapierContext>,
+    sensors: Query<&Parent>,
+    mut doors: Query<(&mut DoorState, &mut Sprite)>,
+    mut characters: Query<(&mut Inventory, &mut Transform, Option<&mut Velocity>)>,
+) {
+    let character = trigger.event().character;
+    let door = rapier
+        .intersection_pairs_with(character)
+        .filter(|(_, _, intersecting)| *intersecting)
+        .map(|(first, second, _)| if first == character { second } else { first })
+        .filter_map(|sensor| sensors.get(sensor).map(Parent::get).ok())
+        .find(|door| doors.contains(*door));
+    let Some(door) = door else { return; };
+    let Ok((mut inventory, mut transform, velocity)) = characters.get_mut(character) else { return; };
+    let Ok((mut state, mut sprite)) = doors.get_mut(door) else { return; };
+
+    if state.locked {
+        if !inventory.take(Item::Key) {
+            commands.trigger(LockedDoorUsed);
+            return;
+        }
+        state.locked = false;
+        sprite.color = UNLOCKED_COLOR;
+    }
+
+    if state.destination.is_none() {
+        if !state.opened {
+            state.opened = true;
+            commands.entity(door).remove::<Collider>().insert(Visibility::Hidden);
+        }
+        return;
+    }
+    let Some(target) = state.target else { return; };
+
+    // the level streaming loads the level the player lands in
+    transform.translation = target.extend(transform.translation.z);
+    if let Some(mut velocity) = velocity {
+        velocity.linvel = Vec2::ZERO;
+    }
+}
+
+/// Shows that the door is locked at the bottom of the screen for a moment.
+fn show_locked_message(
+    _trigger: Trigger<LockedDoorUsed>,
+    mut commands: Commands,
+    fonts: Res<FontHandles>,
+    mut messages: Query<&mut LockedMessage>,
+) {
+    if let Ok(mut message) = messages.get_single_mut() {
+        message.0.reset();
+        return;
+    }
+    commands
+        .spawn((
+            StateScoped(GameState::Playing),
+            LockedMessage(Timer::new(LOCKED_MESSAGE_TIME, TimerMode::Once)),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(48.0),
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|root| {
+            root.spawn(TextBundle::from_section(
+                "This door is locked, find a key to open it.",
+                TextStyle { font: fonts.default_font(), font_size: 24.0, ..default() },
+            ));
+        });
+}
+
+fn hide_locked_message(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut messages: Query<(Entity, &mut LockedMessage)>,
+) {
+    for (entity, mut message) in messages.iter_mut() {
+        if message.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    math::Vec2,
+    prelude::{Commands, Component, Entity, GlobalTransform, Has, IntoSystemConfigs, Query, Res, Transform, With, Without},
+    sprite::Sprite,
+    time::Time
+};
+use bevy_ecs_ldtk::{prelude::LdtkFields, EntityInstance};
+use bevy_rapier2d::{
+    pipeline::QueryFilter,
+    plugin::RapierContext,
+    prelude::{CollisionGroups, Group, KinematicCharacterController}
+};
+
+use crate::{
+    character::PlayerTag,
+    collision::LocalGroupNames,
+    game_flow::Gameplay,
+    unsorted::enums::Behaviour
+};
+use super::patrol::Patrol;
+
+/// Enemies chase faster than they patrol.
+pub const CHASE_SPEED_MULTIPLIER: f32 = 2.0;
+const DEFAULT_SPEED: f32 = 16.0;
+const DEFAULT_RADIUS: f32 = 64.0;
+const DEFAULT_GIVE_UP_TIME: f32 = 2.0;
+
+/// Aggressive enemies leave their patrol to chase the player once they see them.
+///
+/// Flying enemies fly straight at the player and back to where they left their patrol,
+/// the [ground walker](super::ground_walker) follows the player along the ground and picks its patrol back up from wherever it gave up.
+pub struct ChasePlugin;
+impl Plugin for ChasePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (detect_player, fly_to_target).chain().in_set(Gameplay));
+    }
+}
+
+/// Set with the `behaviour`, `detection_radius` and `give_up_time` fields in LDtk.
+#[derive(Component, Debug)]
+pub struct Chaser {
+    radius: f32,
+    /// Seconds the enemy keeps chasing after losing sight of the player.
+    give_up_time: f32,
+    unseen: f32,
+    /// Where the enemy left its patrol.
+    origin: Vec2,
+}
+
+impl Chaser {
+    /// `None` for enemies that aren't aggressive.
+    pub fn from_ldtk(entity_instance: &EntityInstance) -> Option<Self> {
+        let behaviour = entity_instance
+            .get_maybe_enum_field("behaviour")
+            .ok()
+            .and_then(Option::as_ref)
+            .and_then(|behaviour| behaviour.parse().ok())
+            .unwrap_or(Behaviour::Passive);
+        if behaviour != Behaviour::Aggressive { return None; }
+
+        let float_field = |field| entity_instance.get_maybe_float_field(field).ok().copied().flatten();
+        Some(Self {
+            radius: float_field("detection_radius").unwrap_or(DEFAULT_RADIUS).max(0.0),
+            give_up_time: float_field("give_up_time").unwrap_or(DEFAULT_GIVE_UP_TIME).max(0.0),
+            unseen: 0.0,
+            origin: Vec2::ZERO,
+        })
+    }
+}
+
+/// An enemy sees the player when they are within `radius` and no level geometry is in between.
+pub fn sees_player(rapier: &RapierContext, enemy: Entity, position: Vec2, player: Entity, player_position: Vec2, radius: f32) -> bool {
+    let offset = player_position - position;
+    let distance = offset.length();
+    if distance > radius { return false; }
+    if distance == 0.0 { return true; }
+
+    let filter = QueryFilter::new()
+        .exclude_sensors()
+        .exclude_collider(enemy)
+        // other enemies don't block the view
+        .groups(CollisionGroups::new(Group::TEST_ENEMY, Group::ALL & !(Group::PICKUP | Group::TEST_ENEMY)));
+    rapier
+        .cast_ray(position, offset / distance, distance, true, filter)
+        .is_some_and(|(hit, _)| hit == player)
+}
+
+/// Inserted while an enemy chases the player, the patrol doesn't move it meanwhile.
+#[derive(Component, Debug)]
+pub struct Chasing {
+    /// Where the player was last seen, or where the enemy left its patrol while it returns to it.
+    pub target: Vec2,
+    pub returning: bool,
+}
+
+fn detect_player(
+    mut commands: Commands,
+    time: Res<Time>,
+    rapier: Res<RapierContext>,
+    player: Query<(Entity, &GlobalTransform), With<PlayerTag>>,
+    mut chasers: Query<(Entity, &mut Chaser, &GlobalTransform, Option<&mut Chasing>, Has<KinematicCharacterController>)>,
+) {
+    let Ok((player, player_transform)) = player.get_single() else { return; };
+    let player_position = player_transform.translation().truncate();
+
+    for (entity, mut chaser, transform, chasing, walks) in chasers.iter_mut() {
+        let position = transform.translation().truncate();
+        let sees = sees_player(&rapier, entity, position, player, player_position, chaser.radius);
+        match chasing {
+            Some(mut chasing) if sees => {
+                chasing.target = player_position;
+                chasing.returning = false;
+                chaser.unseen = 0.0;
+            },
+            None if sees => {
+                chaser.origin = position;
+                chaser.unseen = 0.0;
+                commands.entity(entity).insert(Chasing { target: player_position, returning: false });
+            },
+            Some(mut chasing) if !chasing.returning => {
+                chaser.unseen += time.delta_seconds();
+                if chaser.unseen < chaser.give_up_time { continue; }
+
+                // walkers can't fly back, they continue their patrol from where they are
+                if walks {
+                    commands.entity(entity).remove::<Chasing>();
+                }
+                else {
+                    chasing.target = chaser.origin;
+                    chasing.returning = true;
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Flying enemies move in a straight line, like along their patrol.
+fn fly_to_target(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut enemies: Query<(Entity, &Chasing, Option<&Patrol>, &mut Transform, &GlobalTransform, &mut Sprite), Without<KinematicCharacterController>>,
+) {
+    for (entity, chasing, patrol, mut transform, global_transform, mut sprite) in enemies.iter_mut() {
+        let mut speed = patrol.map(Patrol::speed).unwrap_or(DEFAULT_SPEED);
+        if !chasing.returning {
+            speed *= CHASE_SPEED_MULTIPLIER;
+        }
+
+        let offset = chasing.target - global_transform.translation().truncate();
+        let step = speed * time.delta_seconds();
+        if offset.length() <= step {
+            transform.translation += offset.extend(0.0);
+            if chasing.returning {
+                commands.entity(entity).remove::<Chasing>();
+            }
+            continue;
+        }
+        transform.translation += (offset.normalize() * step).extend(0.0);
+
+        // the sprites of the enemies face right
+        let flip_x = offset.x < 0.0;
+        if offset.x != 0.0 && sprite.flip_x != flip_x {
+            sprite.flip_x = flip_x;
+        }
+    }
+}
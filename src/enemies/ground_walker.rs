@@ -0,0 +1,157 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    math::Vec2,
+    prelude::{Bundle, Component, Entity, GlobalTransform, IntoSystemConfigs, Query, Res},
+    sprite::Sprite,
+    time::Time,
+    utils::default
+};
+use bevy_rapier2d::{
+    pipeline::QueryFilter,
+    plugin::{RapierConfiguration, RapierContext},
+    prelude::{CollisionGroups, Group, KinematicCharacterController, KinematicCharacterControllerOutput, QueryFilterFlags}
+};
+
+use crate::{collision::LocalGroupNames, game_flow::Gameplay};
+use super::{chase::{Chasing, CHASE_SPEED_MULTIPLIER}, patrol::Patrol};
+
+/// Speed of walkers without a patrol.
+const DEFAULT_SPEED: f32 = 16.0;
+/// How far ahead of its body a walker looks for walls and ledges.
+const LOOK_AHEAD: f32 = 2.0;
+/// A waypoint counts as reached once the walker is this close to it horizontally.
+const ARRIVAL_DISTANCE: f32 = 1.0;
+
+/// Enemies that walk on the ground with their own character controller instead of flying along their patrol.
+///
+/// Walkers fall with the gravity of the physics and turn around at walls and ledges.
+/// With a patrol they only follow its waypoints horizontally, without one they wander back and forth.
+/// While [chasing](Chasing) the player they head for the player instead and stop at walls and ledges.
+pub struct GroundWalkerPlugin;
+impl Plugin for GroundWalkerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, walk.in_set(Gameplay));
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct GroundWalker {
+    half_size: Vec2,
+    /// Direction the walker wanders in when it has no patrol, `1.0` is right.
+    direction: f32,
+    fall_velocity: f32,
+}
+
+#[derive(Bundle)]
+pub struct GroundWalkerBundle {
+    walker: GroundWalker,
+    controller: KinematicCharacterController,
+}
+
+impl GroundWalkerBundle {
+    /// `half_size` is half the size of the walker's collider, used to look for walls and ledges right next to it.
+    pub fn new(half_size: Vec2) -> Self {
+        Self {
+            walker: GroundWalker { half_size, direction: 1.0, fall_velocity: 0.0 },
+            controller: KinematicCharacterController {
+                // sensors (checkpoints, pickups, ...) shouldn't block the walker
+                filter_flags: QueryFilterFlags::EXCLUDE_SENSORS,
+                filter_groups: Some(CollisionGroups::new(Group::TEST_ENEMY, obstacles())),
+                ..default()
+            },
+        }
+    }
+}
+
+/// Walkers pass through the player and each other, the player's own controller handles running into them.
+fn obstacles() -> Group {
+    Group::ALL & !(Group::PLAYER | Group::PICKUP | Group::TEST_ENEMY)
+}
+
+impl GroundWalker {
+    fn blocked(&self, rapier: &RapierContext, entity: Entity, position: Vec2, direction: f32) -> bool {
+        let filter = QueryFilter::new()
+            .exclude_sensors()
+            .exclude_collider(entity)
+            .groups(CollisionGroups::new(Group::TEST_ENEMY, obstacles()));
+        let reach = self.half_size.x + LOOK_AHEAD;
+
+        let wall = rapier.cast_ray(position, Vec2::X * direction, reach, true, filter).is_some();
+        let ground_ahead = rapier
+            .cast_ray(position + Vec2::X * direction * reach, Vec2::NEG_Y, self.half_size.y + LOOK_AHEAD, true, filter)
+            .is_some();
+        wall || !ground_ahead
+    }
+}
+
+fn walk(
+    time: Res<Time>,
+    physics: Res<RapierConfiguration>,
+    rapier: Res<RapierContext>,
+    mut walkers: Query<(
+        Entity,
+        &mut GroundWalker,
+        &mut KinematicCharacterController,
+        Option<&KinematicCharacterControllerOutput>,
+        &GlobalTransform,
+        Option<&mut Patrol>,
+        Option<&Chasing>,
+        &mut Sprite,
+    )>,
+) {
+    for (entity, mut walker, mut controller, output, global_transform, patrol, chasing, mut sprite) in walkers.iter_mut() {
+        let grounded = output.is_some_and(|output| output.grounded);
+        let position = global_transform.translation().truncate();
+        let mut speed = DEFAULT_SPEED;
+
+        let mut direction = walker.direction;
+        let mut route = None;
+        if let Some(patrol) = patrol {
+            speed = patrol.speed();
+            route = Some(patrol).filter(|patrol| patrol.has_route());
+        }
+        if let Some(chasing) = chasing {
+            let distance = chasing.target.x - position.x;
+            direction = if distance.abs() <= ARRIVAL_DISTANCE { 0.0 } else { distance.signum() };
+            speed *= CHASE_SPEED_MULTIPLIER;
+        }
+        else if let Some(patrol) = route.as_mut() {
+            let distance = patrol.target().x - position.x;
+            direction = if !patrol.is_moving() {
+                0.0
+            }
+            else if distance.abs() <= ARRIVAL_DISTANCE {
+                patrol.arrive();
+                0.0
+            }
+            else {
+                distance.signum()
+            };
+        }
+
+        // in the air the walker keeps going, so it doesn't turn around while falling off a ledge
+        if direction != 0.0 && grounded && walker.blocked(&rapier, entity, position, direction) {
+            match route.as_mut() {
+                // the walker waits at the edge until it sees the player elsewhere or gives up
+                _ if chasing.is_some() => {},
+                // a waypoint that can't be reached along the ground is skipped
+                Some(patrol) => patrol.arrive(),
+                None => walker.direction = -walker.direction,
+            }
+            direction = 0.0;
+        }
+
+        if grounded {
+            walker.fall_velocity = 0.0;
+        }
+        // pushing down while grounded keeps the controller snapped to the ground
+        walker.fall_velocity += physics.gravity.y * time.delta_seconds();
+        controller.translation = Some(Vec2::new(direction * speed, walker.fall_velocity) * time.delta_seconds());
+
+        // the sprites of the enemies face right
+        let flip_x = direction < 0.0;
+        if direction != 0.0 && sprite.flip_x != flip_x {
+            sprite.flip_x = flip_x;
+        }
+    }
+}
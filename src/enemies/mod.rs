@@ -1,15 +1,33 @@
 mod test_enemy;
 mod entity_bundles;
+mod patrol;
+mod ground_walker;
+mod chase;
+mod shooter;
 
 use test_enemy::TestEnemyPlugin;
-use bevy::app::Plugin;
+use patrol::PatrolPlugin;
+use ground_walker::GroundWalkerPlugin;
+use chase::ChasePlugin;
+use shooter::ShooterPlugin;
+use bevy::{app::Plugin, prelude::{Entity, Event}};
 
 pub use entity_bundles::ObservableColliderBundle;
 pub use entity_bundles::ColliderBundle;
 
+/// Triggered on an enemy right before it is despawned because the player defeated it.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Defeated;
+
+/// Triggered on a character that defeated an enemy by landing on it.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Stomped {
+    pub enemy: Entity,
+}
+
 pub struct EnemyPlugin;
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_plugins(TestEnemyPlugin);
+        app.add_plugins((TestEnemyPlugin, PatrolPlugin, GroundWalkerPlugin, ChasePlugin, ShooterPlugin));
     }
 }
\ No newline at end of file
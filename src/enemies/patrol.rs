@@ -0,0 +1,293 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::Assets,
+    math::{IVec2, Vec2},
+    prelude::{Commands, Component, Entity, GlobalTransform, IntoSystemConfigs, Query, Res, Transform, Without},
+    sprite::Sprite,
+    time::Time
+};
+use bevy_ecs_ldtk::{assets::LdtkProject, prelude::LdtkFields, EntityInstance};
+use bevy_rapier2d::prelude::KinematicCharacterController;
+
+use crate::{
+    game_flow::Gameplay,
+    unsorted::{
+        enums::{Easing, PatrolMode},
+        ldtk_coords::{entity_world_center, find_entity, ldtk_point_to_world},
+        ldtk_level_handler::WorldHandle
+    }
+};
+use super::chase::Chasing;
+
+const DEFAULT_SPEED: f32 = 16.0;
+
+/// Moves enemies along the `patrol` points of their LDtk entity.
+///
+/// Enemies with a character controller move themselves, they only use the patrol for its targets.
+/// Enemies that are [chasing](Chasing) the player pick up their patrol once they return.
+pub struct PatrolPlugin;
+impl Plugin for PatrolPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            // the patrol is resolved after moving, so the global transform of a new enemy is up to date when it starts to move
+            (wait_at_waypoints, move_patrol, resolve_patrol).chain(),
+            face_travel_direction.after(move_patrol),
+        ).in_set(Gameplay));
+    }
+}
+
+/// The patrol as set up in LDtk, it becomes a [Patrol] once the level of the enemy is known.
+#[derive(Component, Debug, Clone)]
+pub struct PatrolSettings {
+    points: Vec<IVec2>,
+    waits: Vec<f32>,
+    mode: PatrolMode,
+    easing: Easing,
+    speed: f32,
+}
+
+impl Default for PatrolSettings {
+    fn default() -> Self {
+        Self { points: Vec::new(), waits: Vec::new(), mode: PatrolMode::Loop, easing: Easing::Linear, speed: DEFAULT_SPEED }
+    }
+}
+
+impl PatrolSettings {
+    /// Reads the `patrol`, `patrol_mode`, `patrol_wait`, `patrol_speed` and `patrol_easing` fields, missing fields keep their default.
+    pub fn from_ldtk(entity_instance: &EntityInstance) -> Self {
+        let enum_field = |field| entity_instance.get_maybe_enum_field(field).ok().and_then(Option::as_ref);
+        let default = Self::default();
+        Self {
+            points: entity_instance
+                .get_maybe_points_field("patrol")
+                .map(|points| points.iter().flatten().copied().collect())
+                .unwrap_or_default(),
+            waits: entity_instance
+                .get_maybe_floats_field("patrol_wait")
+                .map(|waits| waits.iter().map(|wait| wait.unwrap_or_default().max(0.0)).collect())
+                .unwrap_or_default(),
+            mode: enum_field("patrol_mode").and_then(|mode| mode.parse().ok()).unwrap_or(default.mode),
+            easing: enum_field("patrol_easing").and_then(|easing| easing.parse().ok()).unwrap_or(default.easing),
+            speed: entity_instance.get_maybe_float_field("patrol_speed").ok().copied().flatten().unwrap_or(default.speed),
+        }
+    }
+}
+
+/// Waypoints in world space, the first one is where the enemy was placed.
+#[derive(Component, Debug)]
+pub struct Patrol {
+    targets: Vec<Vec2>,
+    /// Seconds to wait at each target.
+    waits: Vec<f32>,
+    mode: PatrolMode,
+    easing: Easing,
+    speed: f32,
+    from: usize,
+    to: usize,
+    backwards: bool,
+    travelled: f32,
+    waiting: f32,
+    finished: bool,
+}
+
+impl Patrol {
+    fn new(targets: Vec<Vec2>, settings: &PatrolSettings) -> Self {
+        Self {
+            waiting: settings.waits.first().copied().unwrap_or_default(),
+            targets,
+            waits: settings.waits.clone(),
+            mode: settings.mode,
+            easing: settings.easing,
+            speed: settings.speed,
+            from: 0,
+            to: 1,
+            backwards: false,
+            travelled: 0.0,
+            finished: false,
+        }
+    }
+
+    /// An enemy without patrol points stands still.
+    pub fn is_moving(&self) -> bool {
+        !self.finished && self.waiting <= 0.0 && self.has_route()
+    }
+
+    /// Whether there is a waypoint besides the one the enemy was placed at.
+    pub fn has_route(&self) -> bool { self.targets.len() > 1 }
+
+    /// Horizontal direction of the current segment.
+    pub fn direction(&self) -> f32 {
+        self.targets[self.to].x - self.targets[self.from].x
+    }
+
+    /// The waypoint the enemy is heading to.
+    pub fn target(&self) -> Vec2 { self.targets[self.to] }
+    pub fn speed(&self) -> f32 { self.speed }
+
+    /// Continues with the next waypoint after waiting at the current one.
+    pub fn arrive(&mut self) {
+        self.waiting = self.waits.get(self.to).copied().unwrap_or_default();
+        self.travelled = 0.0;
+        self.from = self.to;
+
+        let last = self.targets.len() - 1;
+        match self.mode {
+            PatrolMode::Loop => self.to = (self.to + 1) % self.targets.len(),
+            PatrolMode::PingPong => {
+                if self.to == last { self.backwards = true; }
+                if self.to == 0 { self.backwards = false; }
+                self.to = if self.backwards { self.to - 1 } else { self.to + 1 };
+            },
+            PatrolMode::OneShot => {
+                if self.to == last { self.finished = true; } else { self.to += 1; }
+            },
+        }
+    }
+}
+
+impl Easing {
+    /// Maps the fraction of the time spent on a segment to the fraction of the distance covered.
+    fn ease(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+fn resolve_patrol(
+    mut commands: Commands,
+    enemies: Query<(Entity, &PatrolSettings, &EntityInstance)>,
+    world: Res<WorldHandle>,
+    projects: Res<Assets<LdtkProject>>,
+) {
+    let Some(project) = projects.get(&world.handle()) else { return; };
+    let grid_size = project.json_data().default_grid_size;
+    for (entity, settings, entity_instance) in enemies.iter() {
+        let Some((level, _)) = find_entity(project.json_data(), &entity_instance.iid) else { continue; };
+        let targets = [entity_world_center(level, entity_instance)]
+            .into_iter()
+            .chain(settings.points.iter().map(|point| ldtk_point_to_world(level, *point, grid_size)))
+            .collect();
+        commands
+            .entity(entity)
+            .insert(Patrol::new(targets, settings))
+            .remove::<PatrolSettings>();
+    }
+}
+
+fn wait_at_waypoints(time: Res<Time>, mut enemies: Query<&mut Patrol>) {
+    for mut patrol in enemies.iter_mut() {
+        if patrol.waiting > 0.0 {
+            patrol.waiting -= time.delta_seconds();
+        }
+    }
+}
+
+/// The patrol targets are in world space, the enemy is moved relative to its level.
+fn move_patrol(
+    time: Res<Time>,
+    mut enemies: Query<(&mut Patrol, &mut Transform, &GlobalTransform), (Without<KinematicCharacterController>, Without<Chasing>)>,
+) {
+    for (mut patrol, mut transform, global_transform) in enemies.iter_mut() {
+        if !patrol.is_moving() { continue; }
+
+        let (from, to) = (patrol.targets[patrol.from], patrol.targets[patrol.to]);
+        let length = from.distance(to);
+        patrol.travelled = (patrol.travelled + patrol.speed * time.delta_seconds()).min(length);
+        let progress = if length > 0.0 { patrol.travelled / length } else { 1.0 };
+
+        let position = from.lerp(to, patrol.easing.ease(progress));
+        transform.translation += (position - global_transform.translation().truncate()).extend(0.0);
+        if progress >= 1.0 {
+            patrol.arrive();
+        }
+    }
+}
+
+/// The sprites of the enemies face right.
+fn face_travel_direction(mut enemies: Query<(&Patrol, &mut Sprite), (Without<KinematicCharacterController>, Without<Chasing>)>) {
+    for (patrol, mut sprite) in enemies.iter_mut() {
+        if !patrol.is_moving() || patrol.direction() == 0.0 { continue; }
+        let flip_x = patrol.direction() < 0.0;
+        if sprite.flip_x != flip_x {
+            sprite.flip_x = flip_x;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patrol(mode: PatrolMode, waits: Vec<f32>) -> Patrol {
+        let settings = PatrolSettings { mode, waits, ..Default::default() };
+        Patrol::new(vec![Vec2::ZERO, Vec2::X, Vec2::Y], &settings)
+    }
+
+    /// The waypoints the patrol heads to after arriving `count` times.
+    fn route(patrol: &mut Patrol, count: usize) -> Vec<usize> {
+        (0..count).map(|_| { patrol.arrive(); patrol.to }).collect()
+    }
+
+    #[test]
+    fn loop_returns_to_the_start() {
+        let mut patrol = patrol(PatrolMode::Loop, Vec::new());
+        assert_eq!(route(&mut patrol, 5), [2, 0, 1, 2, 0]);
+        assert!(patrol.is_moving());
+    }
+
+    #[test]
+    fn ping_pong_turns_at_both_ends() {
+        let mut patrol = patrol(PatrolMode::PingPong, Vec::new());
+        assert_eq!(route(&mut patrol, 6), [2, 1, 0, 1, 2, 1]);
+    }
+
+    #[test]
+    fn one_shot_stops_at_the_last_target() {
+        let mut patrol = patrol(PatrolMode::OneShot, Vec::new());
+        patrol.arrive();
+        assert!(patrol.is_moving());
+        patrol.arrive();
+        assert_eq!(patrol.from, 2);
+        assert!(!patrol.is_moving());
+    }
+
+    #[test]
+    fn waits_at_each_target() {
+        let mut patrol = patrol(PatrolMode::Loop, vec![1.0, 2.0]);
+        assert_eq!(patrol.waiting, 1.0);
+        assert!(!patrol.is_moving());
+        patrol.arrive();
+        assert_eq!(patrol.waiting, 2.0);
+        // targets without a wait of their own are left right away
+        patrol.arrive();
+        assert_eq!(patrol.waiting, 0.0);
+        assert!(patrol.is_moving());
+    }
+
+    #[test]
+    fn a_single_target_stands_still() {
+        let patrol = Patrol::new(vec![Vec2::ZERO], &PatrolSettings::default());
+        assert!(!patrol.is_moving());
+    }
+
+    #[test]
+    fn easing_starts_and_ends_at_the_targets() {
+        for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert_eq!(easing.ease(0.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.ease(1.0), 1.0, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn easing_curves() {
+        assert_eq!(Easing::Linear.ease(0.25), 0.25);
+        assert!(Easing::EaseIn.ease(0.25) < 0.25);
+        assert!(Easing::EaseOut.ease(0.25) > 0.25);
+        assert_eq!(Easing::EaseInOut.ease(0.5), 0.5);
+        assert!(Easing::EaseInOut.ease(0.25) < 0.25 && Easing::EaseInOut.ease(0.75) > 0.75);
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::Assets,
+    math::Rect,
+    prelude::{Commands, Component, Entity, GlobalTransform, IntoSystemConfigs, Query, Res, Transform, With},
+    sprite::{Sprite, SpriteBundle},
+    time::{Time, Timer, TimerMode},
+    utils::default
+};
+use bevy_ecs_ldtk::{assets::LdtkProject, ldtk::TilesetRectangle, prelude::LdtkFields, EntityInstance};
+use bevy_rapier2d::plugin::RapierContext;
+
+use crate::{
+    character::PlayerTag,
+    game_flow::Gameplay,
+    projectile::{Projectile, ProjectileBundle},
+    unsorted::ldtk_level_handler::WorldHandle
+};
+use super::chase::sees_player;
+
+const DEFAULT_COOLDOWN: f32 = 1.5;
+const DEFAULT_RANGE: f32 = 96.0;
+
+const PROJECTILE_SPEED: f32 = 96.0;
+/// The longer side of the projectile's sprite, the tile is scaled down to it.
+const PROJECTILE_SIZE: f32 = 8.0;
+const PROJECTILE_LIFETIME: Duration = Duration::from_secs(3);
+const DAMAGE: u32 = 1;
+
+/// Ranged enemies shoot at the player whenever they see them and their cooldown is over.
+pub struct ShooterPlugin;
+impl Plugin for ShooterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, shoot.in_set(Gameplay));
+    }
+}
+
+/// Set with the `projectile`, `shoot_cooldown` and `shoot_range` fields in LDtk.
+#[derive(Component, Debug)]
+pub struct Shooter {
+    /// The projectile's sprite in one of the project's tilesets.
+    sprite: TilesetRectangle,
+    cooldown: Timer,
+    range: f32,
+}
+
+impl Shooter {
+    /// `None` for enemies without a projectile.
+    pub fn from_ldtk(entity_instance: &EntityInstance) -> Option<Self> {
+        let sprite = entity_instance.get_maybe_tile_field("projectile").ok().cloned().flatten()?;
+        let float_field = |field| entity_instance.get_maybe_float_field(field).ok().copied().flatten();
+        let cooldown = float_field("shoot_cooldown").unwrap_or(DEFAULT_COOLDOWN).max(0.0);
+        Some(Self {
+            sprite,
+            cooldown: Timer::new(Duration::from_secs_f32(cooldown), TimerMode::Once),
+            range: float_field("shoot_range").unwrap_or(DEFAULT_RANGE).max(0.0),
+        })
+    }
+}
+
+fn shoot(
+    mut commands: Commands,
+    time: Res<Time>,
+    rapier: Res<RapierContext>,
+    world: Res<WorldHandle>,
+    projects: Res<Assets<LdtkProject>>,
+    player: Query<(Entity, &GlobalTransform), With<PlayerTag>>,
+    mut shooters: Query<(Entity, &mut Shooter, &GlobalTransform)>,
+) {
+    let Ok((player, player_transform)) = player.get_single() else { return; };
+    let Some(project) = projects.get(&world.handle()) else { return; };
+    let player_position = player_transform.translation().truncate();
+
+    for (entity, mut shooter, transform) in shooters.iter_mut() {
+        if !shooter.cooldown.tick(time.delta()).finished() { continue; }
+        let position = transform.translation();
+        if !sees_player(&rapier, entity, position.truncate(), player, player_position, shooter.range) { continue; }
+        shooter.cooldown.reset();
+
+        let Some(texture) = project.tileset_map().get(&shooter.sprite.tileset_uid) else { continue; };
+        let TilesetRectangle { x, y, w, h, .. } = shooter.sprite;
+        let rect = Rect::new(x as f32, y as f32, (x + w) as f32, (y + h) as f32);
+        let size = rect.size() * (PROJECTILE_SIZE / rect.size().max_element());
+        let direction = (player_position - position.truncate()).normalize_or_zero();
+
+        commands.spawn(ProjectileBundle::new(
+            Projectile::new(direction * PROJECTILE_SPEED, DAMAGE, PROJECTILE_LIFETIME),
+            SpriteBundle {
+                texture: texture.clone(),
+                sprite: Sprite {
+                    rect: Some(rect),
+                    custom_size: Some(size),
+                    flip_x: direction.x < 0.0,
+                    ..default()
+                },
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            size.min_element() / 2.0,
+        ));
+    }
+}
@@ -1,11 +1,11 @@
 use std::{fmt::Debug, marker::PhantomData, time::Duration};
 
-use bevy::{app::{App, Plugin, PostStartup, PreStartup, Startup, Update}, ecs::{component::{self, ComponentId, Tick}, observer, query, system::{IntoObserverSystem, ObserverSystem}, world::{self, DeferredWorld}}, log::error, math::{IVec2, Vec2}, prelude::{in_state, BuildChildren, Bundle, Commands, Component, DespawnRecursiveExt, Entity, Event, FromWorld, IntoSystem, IntoSystemConfigs, Local, Mut, NextState, Observer, Query, Res, ResMut, Resource, SpatialBundle, Transform, Trigger, With, World}, reflect::GetField, scene::ron::value, sprite::{Sprite, SpriteBundle}, text::{Text, Text2dBundle, TextStyle}, time::{Stopwatch, Time}, ui::Style, utils::{default, info}};
-use bevy_ecs_ldtk::{app::LdtkEntityAppExt, prelude::LdtkFields, utils::grid_coords_to_translation, EntityInstance, GridCoords, LdtkEntity, };
+use bevy::{app::{App, Plugin, PostStartup, PreStartup, Startup, Update}, ecs::{component::{self, ComponentId, Tick}, observer, query, system::{IntoObserverSystem, ObserverSystem}, world::{self, DeferredWorld}}, log::error, math::{IVec2, Vec2}, prelude::{in_state, BuildChildren, Bundle, Commands, Component, DespawnRecursiveExt, Entity, Event, FromWorld, IntoSystem, IntoSystemConfigs, Local, Mut, NextState, Observer, Parent, Query, Res, ResMut, Resource, SpatialBundle, Transform, Trigger, With, Without, World}, reflect::GetField, scene::ron::value, sprite::{Sprite, SpriteBundle}, text::{Text, Text2dBundle, TextStyle}, time::{Stopwatch, Time}, ui::Style, utils::{default, info}};
+use bevy_ecs_ldtk::{app::LdtkEntityAppExt, prelude::LdtkFields, utils::grid_coords_to_translation, EntityInstance, GridCoords, LdtkEntity, LevelIid};
 use bevy_rapier2d::{na::Translation, prelude::{ActiveCollisionTypes, ActiveEvents, Collider, CollisionGroups, Group, Sensor}};
 
-use crate::{character::{CharacterColision, ImageHandles}, collision::LocalGroupNames, game_flow::GameState, unsorted::{BevyPromiseResolver, Promise, PromiseProcedure}};
-use super::{ColliderBundle, ObservableColliderBundle};
+use crate::{character::{Damage, Dead, Health, ImageHandles, InvincibilityTime}, collision::{CharacterColisionDispatch, ColisionWith, LocalGroupNames}, game_flow::{GameState, Gameplay, LevelCleared, LevelProgression}, items::Loot, unsorted::{entities, enums::Movement, ldtk_level_handler::owning_level, BevyPromiseResolver, Promise, PromiseProcedure}};
+use super::{chase::Chaser, ground_walker::GroundWalkerBundle, patrol::PatrolSettings, shooter::Shooter, ColliderBundle, Defeated, ObservableColliderBundle, Stomped};
 
 #[derive(Default, Bundle, LdtkEntity)]
 struct TestEnemyBundle {
@@ -15,7 +15,35 @@ struct TestEnemyBundle {
     test_enemy_tag: TestEnemy,
 
     #[with(retrieve_patrol)]
-    patrol: Patrol,
+    patrol: PatrolSettings,
+
+    #[with(retrieve_movement)]
+    movement: MovementKind,
+
+    #[with(retrieve_loot)]
+    loot: Loot,
+}
+
+fn retrieve_loot(entity_instance: &EntityInstance) -> Loot {
+    Loot::from_ldtk_field(entity_instance, "loot")
+}
+
+/// Set with the `movement` field in LDtk, flying mobs move straight between their patrol points, walking ones stay on the ground.
+#[derive(Component, Clone, Copy, Debug)]
+struct MovementKind(Movement);
+
+impl Default for MovementKind {
+    fn default() -> Self { Self(Movement::Flying) }
+}
+
+fn retrieve_movement(entity_instance: &EntityInstance) -> MovementKind {
+    entity_instance
+        .get_maybe_enum_field("movement")
+        .ok()
+        .and_then(Option::as_ref)
+        .and_then(|movement| movement.parse().ok())
+        .map(MovementKind)
+        .unwrap_or_default()
 }
 
 #[derive(Default, Component)]
@@ -25,6 +53,13 @@ impl TestEnemy {
     const CAPSULE_HEIGHT: f32 = 8.0;
 
     const HALF_CAPSULE_HEIGHT: f32 = Self::CAPSULE_HEIGHT / 2.0;
+
+    const DAMAGE: u32 = 1;
+    const KNOCKBACK_VELOCITY: f32 = 150.0;
+
+    const DEFAULT_HEALTH: u32 = 1;
+    /// Short enough for a second attack to hit after the cooldown.
+    const INVINCIBILITY: Duration = Duration::from_millis(300);
 }
 
 #[derive(Component)]
@@ -39,13 +74,11 @@ impl Plugin for TestEnemyPlugin
 {
     fn build(&self, app: &mut bevy::prelude::App) {
         app
-            .register_ldtk_entity::<TestEnemyBundle>("Mob")    
+            .register_ldtk_entity::<TestEnemyBundle>(entities::MOB)    
             .register_promise::<TestEnemy>()
-            .add_systems(Update, handle_completion.run_if(in_state(GameState::Playing)).run_if(all_enemies_dead))            
-            .add_systems(Update, move_patrol)            
+            .dispatch_character_colisions::<TestEnemy, _>(character_colision_handler)
+            .add_systems(Update, defeat_dead_enemies.in_set(Gameplay))            
             .add_systems(Startup, |world: &mut World| {
-                world.register_component_hooks::<Patrol>().on_add(added_patrol);
-                world.register_component_hooks::<Transform>().on_add(added_transform);
                 // world.register_component_hooks::<GridCoords>().on_add(grid_coord_text);
                 // world.register_component_hooks::<Patrol>().on_add(show_patrol_positions);
             })            
@@ -53,15 +86,34 @@ impl Plugin for TestEnemyPlugin
     }
 }
 
-// #! TODO: Make a centrale obsever entity for all test enemies. 
 impl PromiseProcedure for TestEnemy {
     fn resolve_promise(mut world: DeferredWorld, entity: Entity, component_id: ComponentId) {
-        let mut observer = Observer::new(character_colision_handler);
-        observer.watch_entity(entity);
-        world
-            .commands()
-            .entity(entity)
-            .insert(
+        let texture = world.resource::<ImageHandles>().test_enemy.clone_weak();
+        let movement = world.get::<MovementKind>(entity).copied().unwrap_or_default();
+        let chaser = world.get::<EntityInstance>(entity).and_then(Chaser::from_ldtk);
+        let shooter = world.get::<EntityInstance>(entity).and_then(Shooter::from_ldtk);
+        let health = world
+            .get::<EntityInstance>(entity)
+            .and_then(|instance| instance.get_int_field("health").ok().copied())
+            .map_or(TestEnemy::DEFAULT_HEALTH, |health| health.max(1) as u32);
+        let mut commands = world.commands();
+        let mut entity_commands = commands.entity(entity);
+
+        if movement.0 == Movement::Walking {
+            entity_commands.insert(GroundWalkerBundle::new(Vec2::new(
+                TestEnemy::CORNER_RADIUS,
+                TestEnemy::HALF_CAPSULE_HEIGHT + TestEnemy::CORNER_RADIUS,
+            )));
+        }
+        if let Some(chaser) = chaser {
+            entity_commands.insert(chaser);
+        }
+        if let Some(shooter) = shooter {
+            entity_commands.insert(shooter);
+        }
+
+        entity_commands
+            .insert((
                 ColliderBundle {
                     collider: Collider::capsule_y(TestEnemy::HALF_CAPSULE_HEIGHT, TestEnemy::CORNER_RADIUS),
                     collision_groups: CollisionGroups {
@@ -69,10 +121,15 @@ impl PromiseProcedure for TestEnemy {
                         filters: Group::ALL & !Group::TEST_ENEMY_SENSOR
                     },
                 },
-            )
-            .with_children(|children| {
-                children.spawn(observer);
-            })
+                // flipped by the patrol or the ground walker to face the direction the mob moves in
+                Sprite {
+                    custom_size: Some(Vec2::new(2.0 * TestEnemy::CORNER_RADIUS, TestEnemy::CAPSULE_HEIGHT + 2.0 * TestEnemy::CORNER_RADIUS)),
+                    ..default()
+                },
+                texture,
+                Health::new(health),
+                InvincibilityTime(TestEnemy::INVINCIBILITY),
+            ))
             .remove_by_id(component_id);
 
         // let id = world.get::<EntityInstance>(entity).unwrap();
@@ -80,61 +137,57 @@ impl PromiseProcedure for TestEnemy {
     }
 }
 
-pub fn character_colision_handler(
-    trigger: Trigger<CharacterColision>,
+fn character_colision_handler(
+    trigger: Trigger<ColisionWith<TestEnemy>>,
     mut commands: Commands,
-    mut next_state: ResMut<NextState<GameState>>,
-    // damage query
+    dead: Query<(), With<Dead>>,
 ) {
+    // the character can collide with an enemy several times before it is despawned
+    if dead.contains(trigger.entity()) { return; }
+
     let hit_details = trigger.event().hit.details.unwrap();
     let normal_from_self = hit_details.normal1;
     if normal_from_self.x.abs() == 1.0 || normal_from_self.y.is_sign_negative() {
-        commands.entity(trigger.event().character).despawn_descendants();
-        next_state.set(GameState::Defeated);
+        let knockback = (normal_from_self + Vec2::Y).normalize_or_zero() * TestEnemy::KNOCKBACK_VELOCITY;
+        commands.trigger_targets(Damage { amount: TestEnemy::DAMAGE, knockback }, trigger.event().character);
     }
     else {
-        commands.entity(trigger.entity()).despawn_recursive();
+        commands.trigger_targets(Stomped { enemy: trigger.entity() }, trigger.event().character);
+        commands.entity(trigger.entity()).insert(Dead);
     }    
 }
 
 
-#[derive(Component, Default)]
-struct Patrol {
-    targets: Vec<Vec2>,
-    current_target: usize,
-    speed: f32
-}
-
-
-impl From<Vec<Vec2>> for Patrol {
-    fn from(targets: Vec<Vec2>) -> Self {
-        Patrol {
-            targets, 
-            current_target: 0,
-            speed: 16.0
-        }
+/// Enemies are dead once their health runs out or they are stomped.
+///
+/// Defeating the last enemy of the level being played clears it.
+/// Enemies that disappear because their level is unloaded or respawned aren't defeated, so they don't clear it.
+fn defeat_dead_enemies(
+    mut commands: Commands,
+    dead: Query<Entity, (With<TestEnemy>, With<Dead>)>,
+    alive: Query<Entity, (With<TestEnemy>, Without<Dead>)>,
+    parents: Query<&Parent>,
+    levels: Query<&LevelIid>,
+    progression: Res<LevelProgression>,
+) {
+    if dead.is_empty() { return; }
+    let in_current_level = |enemy| {
+        owning_level(enemy, &parents, &levels).is_some_and(|level| Some(level.as_str()) == progression.current_level_iid())
+    };
+
+    let mut defeated_in_level = false;
+    for enemy in dead.iter() {
+        defeated_in_level |= in_current_level(enemy);
+        commands.trigger_targets(Defeated, enemy);
+        commands.entity(enemy).despawn_recursive();
     }
-}
-
-impl Patrol {
-    pub fn next(&mut self) {
-        self.current_target += 1;
-        self.current_target %= self.targets.len();
+    if defeated_in_level && !alive.iter().any(in_current_level) {
+        commands.trigger(LevelCleared::default());
     }
 }
 
-const MAX_TILE_Y: i32 = 20;
-fn retrieve_patrol(entity_instance: &EntityInstance) -> Patrol {
-    
-    if let Ok(patrol_slice) = entity_instance.get_maybe_points_field("patrol") {
-        patrol_slice.iter().filter_map(|pt|{ 
-            let IVec2 { x, y} =  (*pt)?;
-            Some(grid_coords_to_translation(GridCoords::new(x, MAX_TILE_Y-y), TILESIZE))
-        }).collect::<Vec<Vec2>>().into()
-    }
-    else { 
-        Patrol::default()         
-    }
+fn retrieve_patrol(entity_instance: &EntityInstance) -> PatrolSettings {
+    PatrolSettings::from_ldtk(entity_instance)
 }
 
 fn spawn_coord_text(
@@ -166,25 +219,6 @@ fn grid_coord_text(mut world: DeferredWorld, entity: Entity, _component_id: Comp
     );
 }
 
-fn push_position(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
-    let pos = world.get::<Transform>(entity).expect("A patrol should also have a transform component!").translation.truncate();
-    info(format!("pos: {}", pos));
-    world.get_mut::<Patrol>(entity).expect("This is a hook for on_add patrol!").targets.push(pos);
-}
-
-fn added_patrol(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
-    let Some(Transform { translation, .. }) = world.get::<Transform>(entity) else { return; };
-    let translation = translation.truncate();
-    world.get_mut::<Patrol>(entity).expect("This is a hook for on_add patrol!").targets.push(translation);
-}
-
-fn added_transform(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
-    let Transform { translation, .. } = world.get::<Transform>(entity).expect("This is a hook for on_add transform!");
-    let translation = translation.truncate();
-    let Some(mut patrol) = world.get_mut::<Patrol>(entity) else { return; };
-    patrol.targets.push(translation);
-}
-
     // fn show_patrol_positions(
 //     mut world: DeferredWorld, entity: Entity, _component_id: ComponentId
 // ) {
@@ -222,43 +256,3 @@ fn added_transform(mut world: DeferredWorld, entity: Entity, _component_id: Comp
 
 
 const TILESIZE: IVec2 = IVec2 { x: 16, y: 16 };
-fn move_patrol(
-    time: Res<Time>,
-    mut query: Query<(&mut Patrol, &mut Transform)>,
-    // mut stopwatch: Local<Stopwatch>
-) {
-    // stopwatch.tick(time.delta());
-    // if stopwatch.elapsed() < Duration::from_secs(5) { return; }
-    // stopwatch.reset();
-    for (mut patrol, mut transfrom) in query.iter_mut() {
-        let target = *patrol.targets.get(patrol.current_target).expect("No Patrol targets set?!");
-        // transfrom.translation = target.extend(0.0);
-        // patrol.next();
-        // continue;
-
-        let segment = target - transfrom.translation.truncate();
-        let step = segment.normalize() * patrol.speed * time.delta_seconds();
-        if step.length_squared() > segment.length_squared() {
-            transfrom.translation = target.extend(transfrom.translation.z);
-            patrol.next();
-        }
-        else {
-            transfrom.translation += step.extend(0.0);
-        }
-    }
-}
-
-fn all_enemies_dead(enemies: Query<(), With<TestEnemy>>, mut enemies_spawned_param: Local<bool>) -> bool
-{
-    let enemies_spawned = enemies_spawned_param.clone();
-    if enemies_spawned { enemies.is_empty() } else {
-        *enemies_spawned_param = !enemies.is_empty();
-        return false;
-    }
-}
-
-fn handle_completion(
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    next_state.set(GameState::Completed);
-}
\ No newline at end of file
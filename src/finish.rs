@@ -1,8 +1,8 @@
-use bevy::{app::{Plugin, PreStartup}, ecs::{component::ComponentId, world::DeferredWorld}, prelude::{BuildChildren, Bundle, Entity, NextState, ResMut, SpatialBundle, Transform, Trigger, World}};
-use bevy_ecs_ldtk::{app::LdtkEntityAppExt, LdtkEntity};
+use bevy::{app::{Plugin, PreStartup}, ecs::{component::ComponentId, world::DeferredWorld}, prelude::{BuildChildren, Bundle, Commands, Component, Entity, Local, SpatialBundle, Transform, Trigger, World}};
+use bevy_ecs_ldtk::{app::LdtkEntityAppExt, prelude::LdtkFields, EntityInstance, LdtkEntity};
 use bevy_rapier2d::prelude::{Collider, CollisionEvent, CollisionGroups, Group};
 
-use crate::{character::CharacterColision, collision::LocalGroupNames, enemies::{ColliderBundle, ObservableColliderBundle}, game_flow::GameState, unsorted::{Promise, PromiseProcedure}};
+use crate::{character::CharacterColision, collision::LocalGroupNames, enemies::{ColliderBundle, ObservableColliderBundle}, game_flow::LevelCleared, unsorted::{entities, Promise, PromiseProcedure}};
 
 
 #[derive(Default, Bundle, LdtkEntity)]
@@ -10,6 +10,17 @@ struct FinishBundle {
     // #[sprite_sheet_bundle]
     // sprite_sheet_bundle: LdtkSpriteSheetBundle,
     test_enemy: Promise<Finish>,
+
+    #[with(retrieve_next_level)]
+    next_level: NextLevel,
+}
+
+/// Level to continue with after this finish, set with the `next_level` field in LDtk.
+#[derive(Component, Default, Clone, Debug)]
+struct NextLevel(Option<String>);
+
+fn retrieve_next_level(entity_instance: &EntityInstance) -> NextLevel {
+    NextLevel(entity_instance.get_maybe_string_field("next_level").ok().cloned().flatten())
 }
 
 pub struct FinishPlugin;
@@ -17,7 +28,7 @@ impl Plugin for FinishPlugin
 {
     fn build(&self, app: &mut bevy::prelude::App) {
         app
-            .register_ldtk_entity::<FinishBundle>("Finish")
+            .register_ldtk_entity::<FinishBundle>(entities::FINISH)
             .add_systems(PreStartup, |world: &mut World| { 
                 world
                     .register_component_hooks::<Promise<Finish>>()
@@ -32,6 +43,7 @@ const TILESIZE: f32 = 8.0;
 struct Finish;
 impl PromiseProcedure for Finish {
     fn resolve_promise(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
+        let next_level = world.get::<NextLevel>(entity).cloned().unwrap_or_default().0;
         world
             .commands()
             .entity(entity)
@@ -44,8 +56,11 @@ impl PromiseProcedure for Finish {
                         filters: Group::PLAYER,
                     }
                 ))
-                .observe(|_trigger: Trigger<CharacterColision>, mut next_state: ResMut<NextState<GameState>>| {
-                    next_state.set(GameState::Completed);
+                .observe(move |_trigger: Trigger<CharacterColision>, mut commands: Commands, mut reached: Local<bool>| {
+                    // the character can collide multiple times before the level is switched
+                    if *reached { return; }
+                    *reached = true;
+                    commands.trigger(LevelCleared { next_level: next_level.clone() });
                 });
             });
     }
@@ -24,6 +24,7 @@ pub struct FontHandles {
 
 impl FontHandles {
     pub fn default_font(&self) -> Handle<Font> { self.fira_sans.clone() }
+    pub fn is_loaded(&self, server: &AssetServer) -> bool { server.is_loaded_with_dependencies(&self.fira_sans) }
 }
 
 fn load_fonts(
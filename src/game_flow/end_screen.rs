@@ -0,0 +1,97 @@
+use bevy::{
+    app::{
+        Plugin,
+        Update
+    },
+    prelude::{
+        in_state,
+        BuildChildren,
+        Changed,
+        Commands,
+        Component,
+        Condition,
+        IntoSystemConfigs,
+        NextState,
+        OnEnter,
+        Query,
+        Res,
+        ResMut,
+        StateScoped
+    },
+    ui::Interaction
+};
+
+use crate::font_handing::FontHandles;
+
+use super::{ui::{menu_root, spawn_button, spawn_title}, GameState, LevelProgression};
+
+pub struct EndScreenPlugin;
+impl Plugin for EndScreenPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .add_systems(OnEnter(GameState::Defeated), spawn_defeat_screen)
+            .add_systems(OnEnter(GameState::Completed), spawn_complete_screen)
+            .add_systems(Update, end_screen_actions.run_if(in_state(GameState::Defeated).or_else(in_state(GameState::Completed))));
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+enum EndScreenAction {
+    RetryLevel,
+    NextLevel,
+    MainMenu,
+}
+
+fn spawn_defeat_screen(
+    mut commands: Commands,
+    fonts: Res<FontHandles>,
+) {
+    commands
+        .spawn((StateScoped(GameState::Defeated), menu_root()))
+        .with_children(|root| {
+            spawn_title(root, "You died!", fonts.default_font());
+            spawn_button(root, "Retry level", fonts.default_font(), EndScreenAction::RetryLevel);
+            spawn_button(root, "Main menu", fonts.default_font(), EndScreenAction::MainMenu);
+        });
+}
+
+fn spawn_complete_screen(
+    mut commands: Commands,
+    fonts: Res<FontHandles>,
+    progression: Res<LevelProgression>,
+) {
+    let has_next_level = progression.next_index().is_some();
+    commands
+        .spawn((StateScoped(GameState::Completed), menu_root()))
+        .with_children(|root| {
+            spawn_title(root, "You win!", fonts.default_font());
+            if has_next_level {
+                spawn_button(root, "Next level", fonts.default_font(), EndScreenAction::NextLevel);
+            }
+            spawn_button(root, "Retry level", fonts.default_font(), EndScreenAction::RetryLevel);
+            spawn_button(root, "Main menu", fonts.default_font(), EndScreenAction::MainMenu);
+        });
+}
+
+fn end_screen_actions(
+    buttons: Query<(&Interaction, &EndScreenAction), Changed<Interaction>>,
+    mut progression: ResMut<LevelProgression>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, action) in buttons.iter() {
+        if *interaction != Interaction::Pressed { continue; }
+        match action {
+            // the world is respawned with the current level selection when entering `GameState::Playing`
+            EndScreenAction::RetryLevel => {
+                progression.restart_level();
+                next_state.set(GameState::Playing);
+            },
+            EndScreenAction::NextLevel => {
+                let Some(next) = progression.next_index() else { continue; };
+                progression.select(next);
+                next_state.set(GameState::Playing);
+            },
+            EndScreenAction::MainMenu => next_state.set(GameState::MainMenu),
+        }
+    }
+}
@@ -1,13 +1,29 @@
-use bevy::prelude::States;
+use bevy::prelude::{StateSet, States, SubStates};
 
 
 #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum GameState {
     #[default]
-    // LoadingScreen,
-    // MainMenu,
-    // LoadingLevel // or can this be the loading screen?
+    LoadingScreen, // waits until every asset the levels depend on is loaded
+    MainMenu,
     Playing,
     Completed,
     Defeated,
-}
\ No newline at end of file
+}
+
+/// Only exists while [GameState::Playing], so pausing never triggers `OnExit(GameState::Playing)`.
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[source(GameState = GameState::Playing)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[source(GameState = GameState::MainMenu)]
+pub enum MenuScreen {
+    #[default]
+    Main,
+    LevelSelect,
+}
@@ -0,0 +1,83 @@
+use bevy::{
+    app::{
+        Plugin,
+        Update
+    },
+    prelude::{
+        Changed,
+        Commands,
+        Component,
+        IntoSystemConfigs,
+        OnEnter,
+        Query,
+        Res,
+        StateScoped,
+        TextBundle,
+        With
+    },
+    text::{
+        Text,
+        TextStyle
+    },
+    ui::{
+        PositionType,
+        Style,
+        Val
+    },
+    utils::default
+};
+
+use crate::{
+    character::PlayerTag,
+    font_handing::FontHandles,
+    items::Inventory
+};
+
+use super::{GameState, Gameplay};
+
+/// Shows how many gems the player collected, below the health of the player.
+pub struct GemHudPlugin;
+impl Plugin for GemHudPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .add_systems(OnEnter(GameState::Playing), spawn_gem_hud)
+            .add_systems(Update, update_gem_hud.in_set(Gameplay));
+    }
+}
+
+#[derive(Component, Debug)]
+struct GemText;
+
+fn spawn_gem_hud(
+    mut commands: Commands,
+    fonts: Res<FontHandles>,
+    player: Query<&Inventory, With<PlayerTag>>,
+) {
+    let gems = player.get_single().map(Inventory::gems).unwrap_or_default();
+    commands.spawn((
+        StateScoped(GameState::Playing),
+        GemText,
+        TextBundle::from_section(gem_text(gems), TextStyle { font: fonts.default_font(), font_size: 24.0, ..default() })
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(36.0),
+                left: Val::Px(12.0),
+                ..default()
+            }),
+    ));
+}
+
+/// The inventory of a new player is changed as well, so this also covers the player spawning after the hud.
+fn update_gem_hud(
+    player: Query<&Inventory, (With<PlayerTag>, Changed<Inventory>)>,
+    mut texts: Query<&mut Text, With<GemText>>,
+) {
+    let Ok(inventory) = player.get_single() else { return; };
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = gem_text(inventory.gems());
+    }
+}
+
+fn gem_text(gems: u32) -> String {
+    format!("Gems: {}", gems)
+}
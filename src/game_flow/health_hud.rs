@@ -0,0 +1,85 @@
+use bevy::{
+    app::Plugin,
+    color::Color,
+    prelude::{
+        Commands,
+        Component,
+        OnEnter,
+        Query,
+        Res,
+        StateScoped,
+        TextBundle,
+        Trigger,
+        With
+    },
+    text::{
+        Text,
+        TextStyle
+    },
+    ui::{
+        PositionType,
+        Style,
+        Val
+    },
+    utils::default
+};
+
+use crate::{
+    character::{Health, HealthChanged, PlayerTag},
+    font_handing::FontHandles
+};
+
+use super::GameState;
+
+const HURT_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+
+/// Shows the health of the player, in red when the last change hurt the player.
+pub struct HealthHudPlugin;
+impl Plugin for HealthHudPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .add_systems(OnEnter(GameState::Playing), spawn_health_hud)
+            .observe(update_health_hud);
+    }
+}
+
+#[derive(Component, Debug)]
+struct HealthText;
+
+fn spawn_health_hud(
+    mut commands: Commands,
+    fonts: Res<FontHandles>,
+    player: Query<&Health, With<PlayerTag>>,
+) {
+    // the player usually spawns after the hud, the hud is filled in once its health is announced
+    let text = player.get_single().map(|health| health_text(health.current(), health.max())).unwrap_or_default();
+    commands.spawn((
+        StateScoped(GameState::Playing),
+        HealthText,
+        TextBundle::from_section(text, TextStyle { font: fonts.default_font(), font_size: 24.0, ..default() })
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(8.0),
+                left: Val::Px(12.0),
+                ..default()
+            }),
+    ));
+}
+
+fn update_health_hud(
+    trigger: Trigger<HealthChanged>,
+    player: Query<(), With<PlayerTag>>,
+    mut texts: Query<&mut Text, With<HealthText>>,
+) {
+    if !player.contains(trigger.entity()) { return; }
+    let change = trigger.event();
+    for mut text in texts.iter_mut() {
+        let section = &mut text.sections[0];
+        section.value = health_text(change.current, change.max);
+        section.style.color = if change.current < change.previous { HURT_COLOR } else { Color::WHITE };
+    }
+}
+
+fn health_text(current: u32, max: u32) -> String {
+    format!("Health: {}/{}", current, max)
+}
@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+use bevy::{
+    app::{
+        Plugin,
+        PostUpdate
+    },
+    asset::Assets,
+    log::warn,
+    prelude::{
+        resource_changed,
+        resource_exists,
+        Commands,
+        Condition,
+        Event,
+        IntoSystemConfigs,
+        NextState,
+        OnExit,
+        Res,
+        ResMut,
+        Resource,
+        Trigger
+    }
+};
+use bevy_ecs_ldtk::{assets::LdtkProject, LevelSelection};
+
+use crate::unsorted::ldtk_level_handler::WorldHandle;
+
+use super::GameState;
+
+pub struct LevelProgressionPlugin;
+impl Plugin for LevelProgressionPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .add_systems(OnExit(GameState::LoadingScreen), init_level_progression)
+            .add_systems(PostUpdate, select_current_level.run_if(resource_exists::<LevelProgression>.and_then(resource_changed::<LevelProgression>)))
+            .observe(on_level_cleared);
+    }
+}
+
+/// Keeps track of the order of the levels, the level being played and the levels that are already cleared.
+///
+/// The order is the order of the levels in the LDtk project.
+/// This resource persists between levels, so it is only reset when a new game is started.
+#[derive(Resource, Debug, Default)]
+pub struct LevelProgression {
+    levels: Vec<String>,
+    /// Iids of the levels, in the same order as `levels`.
+    iids: Vec<String>,
+    current: usize,
+    cleared: HashSet<String>,
+    player_start_pending: bool,
+}
+
+impl LevelProgression {
+    pub fn levels(&self) -> &[String] { &self.levels }
+    pub fn current_index(&self) -> usize { self.current }
+    pub fn current_level(&self) -> Option<&str> { self.levels.get(self.current).map(String::as_str) }
+    /// Matches the [LevelIid](bevy_ecs_ldtk::LevelIid) of the spawned level being played.
+    pub fn current_level_iid(&self) -> Option<&str> { self.iids.get(self.current).map(String::as_str) }
+    pub fn is_cleared(&self, level: &str) -> bool { self.cleared.contains(level) }
+
+    pub fn next_index(&self) -> Option<usize> { Some(self.current + 1).filter(|next| *next < self.levels.len()) }
+    pub fn index_of(&self, level: &str) -> Option<usize> { self.levels.iter().position(|identifier| identifier == level) }
+
+    /// Jumps to a level, the player is moved to the start of that level.
+    pub fn select(&mut self, index: usize) {
+        if index < self.levels.len() { 
+            self.current = index; 
+            self.player_start_pending = true;
+        }
+    }
+
+    /// Moves the player back to the start of the level being played.
+    pub fn restart_level(&mut self) { self.select(self.current); }
+
+    /// The player walked into a neighbouring level, so the player stays where it is.
+    pub fn enter(&mut self, index: usize) {
+        if index < self.levels.len() { self.current = index; }
+    }
+
+    /// Starts a new game from the first level.
+    pub fn restart(&mut self) {
+        self.select(0);
+        self.cleared.clear();
+    }
+
+    pub fn player_start_pending(&self) -> bool { self.player_start_pending }
+    pub fn player_started(&mut self) { self.player_start_pending = false; }
+
+    fn clear_current(&mut self) {
+        if let Some(level) = self.levels.get(self.current) {
+            self.cleared.insert(level.clone());
+        }
+    }
+}
+
+/// Triggered when the current level is cleared, for example by reaching a finish.
+#[derive(Event, Debug, Default)]
+pub struct LevelCleared {
+    /// Identifier of the level to continue with, the next level in order is used when `None`.
+    pub next_level: Option<String>,
+}
+
+fn init_level_progression(
+    mut commands: Commands,
+    world: Res<WorldHandle>,
+    projects: Res<Assets<LdtkProject>>,
+) {
+    let (levels, iids) = projects
+        .get(&world.handle())
+        .map(|project| project.json_data().levels.iter().map(|level| (level.identifier.clone(), level.iid.clone())).unzip())
+        .unwrap_or_default();
+
+    commands.insert_resource(LevelProgression { levels, iids, ..Default::default() });
+}
+
+fn select_current_level(
+    mut commands: Commands,
+    progression: Res<LevelProgression>,
+) {
+    let Some(level) = progression.current_level() else { return; };
+    commands.insert_resource(LevelSelection::Identifier(level.to_owned()));
+}
+
+fn on_level_cleared(
+    trigger: Trigger<LevelCleared>,
+    mut progression: ResMut<LevelProgression>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    progression.clear_current();
+
+    let named_level = trigger.event().next_level.as_deref().and_then(|level| {
+        let index = progression.index_of(level);
+        if index.is_none() { warn!("Finish points to the unknown level {:?}, continuing with the next level instead!", level); }
+        index
+    });
+
+    match named_level.or_else(|| progression.next_index()) {
+        Some(next) => progression.select(next),
+        None => next_state.set(GameState::Completed), // the last level is cleared
+    }
+}
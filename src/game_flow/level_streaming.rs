@@ -0,0 +1,181 @@
+use bevy::{
+    app::{
+        Plugin,
+        Update
+    },
+    asset::Assets,
+    math::Vec2,
+    prelude::{
+        Commands,
+        GlobalTransform,
+        IntoSystemConfigs,
+        Query,
+        Res,
+        ResMut,
+        Transform,
+        With
+    },
+    utils::default
+};
+use bevy_ecs_ldtk::{assets::LdtkProject, ldtk::{LdtkJson, Level}, prelude::LdtkFields, LdtkSettings, LevelSelection, LevelSpawnBehavior};
+
+use crate::{
+    character::PlayerTag,
+    unsorted::{
+        entities,
+        ldtk_coords::{entity_world_center, ldtk_point_to_world, level_world_rect},
+        ldtk_level_handler::WorldHandle
+    }
+};
+
+use super::{Gameplay, LevelProgression};
+
+/// Levels are spawned at their LDtk world position, together with their neighbours.
+/// The selected level follows the player, so walking into a neighbouring level loads the levels around it.
+///
+/// The player is [Worldly](bevy_ecs_ldtk::Worldly) so it survives the level it was spawned in being unloaded.
+pub struct LevelStreamingPlugin;
+impl Plugin for LevelStreamingPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .insert_resource(LdtkSettings {
+                level_spawn_behavior: LevelSpawnBehavior::UseWorldTranslation { load_level_neighbors: true },
+                ..default()
+            })
+            .add_systems(Update, (place_player_at_level_start, follow_player_through_levels).chain().in_set(Gameplay));
+    }
+}
+
+fn follow_player_through_levels(
+    player: Query<&GlobalTransform, With<PlayerTag>>,
+    world: Res<WorldHandle>,
+    projects: Res<Assets<LdtkProject>>,
+    mut progression: ResMut<LevelProgression>,
+) {
+    if progression.player_start_pending() { return; }
+    let Ok(player) = player.get_single() else { return; };
+    let Some(project) = projects.get(&world.handle()) else { return; };
+
+    let position = player.translation().truncate();
+    let Some(level) = project.json_data().levels.iter().find(|level| level_world_rect(level).contains(position)) else { return; };
+    let Some(index) = progression.index_of(&level.identifier) else { return; };
+    if index != progression.current_index() {
+        progression.enter(index);
+    }
+}
+
+/// Moves the player to the `player_start` of the selected level, when the level is selected from a menu or a finish.
+/// Levels without a `player_start` use the position of their `Player` entity, if they have one.
+///
+/// The player is only spawned with the level holding the `Player` entity, so while there is no player yet,
+/// that level is selected. Selecting the level to play again is left to the [LevelProgression].
+fn place_player_at_level_start(
+    mut commands: Commands,
+    mut player: Query<&mut Transform, With<PlayerTag>>,
+    world: Res<WorldHandle>,
+    projects: Res<Assets<LdtkProject>>,
+    selection: Res<LevelSelection>,
+    mut progression: ResMut<LevelProgression>,
+) {
+    if !progression.player_start_pending() { return; }
+    let Some(project) = projects.get(&world.handle()) else { return; };
+    let Ok(mut transform) = player.get_single_mut() else {
+        // the player isn't spawned yet, its level might not be loaded
+        if let Some(level) = player_level(project.json_data()) {
+            let player_selection = LevelSelection::Identifier(level.identifier.clone());
+            if *selection != player_selection { commands.insert_resource(player_selection); }
+        }
+        return;
+    };
+    let Some(level) = project.json_data().levels.get(progression.current_index()) else { return; };
+
+    if let Some(start) = level_start(level, project.json_data().default_grid_size) {
+        transform.translation = start.extend(transform.translation.z);
+    }
+    progression.player_started();
+}
+
+/// The level holding the `Player` entity.
+fn player_level(project: &LdtkJson) -> Option<&Level> {
+    project.levels.iter().find(|level| player_entity_center(level).is_some())
+}
+
+fn player_entity_center(level: &Level) -> Option<Vec2> {
+    level.layer_instances.iter().flatten()
+        .flat_map(|layer| layer.entity_instances.iter())
+        .find(|entity| entity.identifier == entities::PLAYER)
+        .map(|entity| entity_world_center(level, entity))
+}
+
+/// Where the player starts in `level`: its `player_start`, or else its `Player` entity.
+fn level_start(level: &Level, grid_size: i32) -> Option<Vec2> {
+    match level.get_maybe_point_field("player_start") {
+        Ok(Some(start)) => Some(ldtk_point_to_world(level, *start, grid_size)),
+        _ => player_entity_center(level),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::IVec2;
+    use bevy_ecs_ldtk::ldtk::{EntityInstance, FieldInstance, FieldValue, LayerInstance};
+
+    use super::*;
+
+    const GRID_SIZE: i32 = 16;
+
+    /// A 10 by 5 cell level with its top left corner at world position (160, 0), so its bottom is at y = -80.
+    /// Optionally with a `player_start` and a `Player` entity.
+    fn level(player_start: Option<IVec2>, player: Option<IVec2>) -> Level {
+        let field_instances = player_start.map(|start| FieldInstance {
+            identifier: "player_start".to_owned(),
+            tile: None,
+            field_instance_type: "Point".to_owned(),
+            value: FieldValue::Point(Some(start)),
+            def_uid: 0,
+            real_editor_values: Vec::new(),
+        }).into_iter().collect();
+        let entity_instances = player.map(|px| EntityInstance {
+            identifier: entities::PLAYER.to_owned(),
+            px,
+            width: GRID_SIZE,
+            height: GRID_SIZE,
+            pivot: Vec2::new(0.5, 1.0),
+            ..default()
+        }).into_iter().collect();
+        Level {
+            world_x: 160,
+            px_wid: 10 * GRID_SIZE,
+            px_hei: 5 * GRID_SIZE,
+            field_instances,
+            layer_instances: Some(vec![LayerInstance { entity_instances, ..default() }]),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn player_start_is_used() {
+        let level = level(Some(IVec2::new(2, 4)), Some(IVec2::new(88, 48)));
+        assert_eq!(level_start(&level, GRID_SIZE), Some(Vec2::new(160.0 + 40.0, -80.0 + 8.0)));
+    }
+
+    #[test]
+    fn player_entity_is_the_fallback() {
+        // the pivot is at the bottom center, so the entity covers the cell (5, 2)
+        let level = level(None, Some(IVec2::new(88, 48)));
+        assert_eq!(level_start(&level, GRID_SIZE), Some(Vec2::new(160.0 + 88.0, -40.0)));
+    }
+
+    #[test]
+    fn level_without_a_start_has_none() {
+        assert_eq!(level_start(&level(None, None), GRID_SIZE), None);
+    }
+
+    #[test]
+    fn player_level_is_the_level_with_the_player_entity() {
+        let mut with_player = level(None, Some(IVec2::new(88, 48)));
+        with_player.identifier = "With_player".to_owned();
+        let project = LdtkJson { levels: vec![level(Some(IVec2::ZERO), None), with_player], ..default() };
+        assert_eq!(player_level(&project).map(|level| level.identifier.as_str()), Some("With_player"));
+    }
+}
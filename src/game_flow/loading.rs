@@ -0,0 +1,59 @@
+use bevy::{
+    app::{
+        Plugin,
+        Update
+    },
+    asset::AssetServer,
+    prelude::{
+        in_state,
+        BuildChildren,
+        Commands,
+        IntoSystemConfigs,
+        NextState,
+        OnEnter,
+        Res,
+        ResMut,
+        StateScoped,
+        TextBundle
+    },
+    text::TextStyle,
+    utils::default
+};
+
+use crate::{character::ImageHandles, font_handing::FontHandles, items::DropTablesHandle, unsorted::ldtk_level_handler::WorldHandle};
+
+use super::{ui::menu_root, GameState};
+
+pub struct LoadingPlugin;
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .add_systems(OnEnter(GameState::LoadingScreen), spawn_loading_text)
+            .add_systems(Update, finish_loading.run_if(in_state(GameState::LoadingScreen)));
+    }
+}
+
+fn spawn_loading_text(mut commands: Commands) {
+    // the font might not be loaded yet, so bevy's build in font is used
+    commands
+        .spawn((StateScoped(GameState::LoadingScreen), menu_root()))
+        .with_children(|root| {
+            root.spawn(TextBundle::from_section("Loading...", TextStyle { font_size: 30.0, ..default() }));
+        });
+}
+
+fn finish_loading(
+    server: Res<AssetServer>,
+    world: Res<WorldHandle>,
+    fonts: Res<FontHandles>,
+    images: Res<ImageHandles>,
+    drop_tables: Res<DropTablesHandle>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let loaded = server.is_loaded_with_dependencies(&world.handle())
+        && fonts.is_loaded(&server)
+        && images.is_loaded(&server)
+        && drop_tables.is_loaded(&server);
+
+    if loaded { next_state.set(GameState::MainMenu); }
+}
@@ -0,0 +1,103 @@
+use bevy::{
+    app::{
+        AppExit,
+        Plugin,
+        Update
+    },
+    prelude::{
+        in_state,
+        BuildChildren,
+        Changed,
+        Commands,
+        Component,
+        EventWriter,
+        IntoSystemConfigs,
+        NextState,
+        OnEnter,
+        Query,
+        Res,
+        ResMut,
+        StateScoped
+    },
+    ui::Interaction
+};
+
+use crate::font_handing::FontHandles;
+
+use super::{ui::{menu_root, spawn_button, spawn_title}, GameState, LevelProgression, MenuScreen};
+
+pub struct MainMenuPlugin;
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .add_systems(OnEnter(MenuScreen::Main), spawn_main_menu)
+            .add_systems(OnEnter(MenuScreen::LevelSelect), spawn_level_select)
+            .add_systems(Update, menu_actions.run_if(in_state(GameState::MainMenu)));
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+enum MenuAction {
+    Start,
+    LevelSelect,
+    Quit,
+    Level(usize),
+    Back,
+}
+
+fn spawn_main_menu(
+    mut commands: Commands,
+    fonts: Res<FontHandles>,
+) {
+    commands
+        .spawn((StateScoped(MenuScreen::Main), menu_root()))
+        .with_children(|root| {
+            spawn_title(root, "PWS", fonts.default_font());
+            spawn_button(root, "Start", fonts.default_font(), MenuAction::Start);
+            spawn_button(root, "Level Select", fonts.default_font(), MenuAction::LevelSelect);
+            spawn_button(root, "Quit", fonts.default_font(), MenuAction::Quit);
+        });
+}
+
+fn spawn_level_select(
+    mut commands: Commands,
+    fonts: Res<FontHandles>,
+    progression: Res<LevelProgression>,
+) {
+    commands
+        .spawn((StateScoped(MenuScreen::LevelSelect), menu_root()))
+        .with_children(|root| {
+            spawn_title(root, "Level Select", fonts.default_font());
+            for (index, identifier) in progression.levels().iter().enumerate() {
+                let mut label = identifier.replace('_', " ");
+                if progression.is_cleared(identifier) { label.push_str(" (cleared)"); }
+                spawn_button(root, &label, fonts.default_font(), MenuAction::Level(index));
+            }
+            spawn_button(root, "Back", fonts.default_font(), MenuAction::Back);
+        });
+}
+
+fn menu_actions(
+    buttons: Query<(&Interaction, &MenuAction), Changed<Interaction>>,
+    mut progression: ResMut<LevelProgression>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut next_screen: ResMut<NextState<MenuScreen>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for (interaction, action) in buttons.iter() {
+        if *interaction != Interaction::Pressed { continue; }
+        match action {
+            MenuAction::Start => {
+                progression.restart();
+                next_state.set(GameState::Playing);
+            },
+            MenuAction::LevelSelect => next_screen.set(MenuScreen::LevelSelect),
+            MenuAction::Quit => { exit.send(AppExit::Success); },
+            MenuAction::Level(index) => {
+                progression.select(*index);
+                next_state.set(GameState::Playing);
+            },
+            MenuAction::Back => next_screen.set(MenuScreen::Main),
+        }
+    }
+}
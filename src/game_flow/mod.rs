@@ -1,3 +1,42 @@
+mod end_screen;
 mod game_state;
+mod gem_hud;
+mod health_hud;
+mod level_progression;
+mod level_streaming;
+mod loading;
+mod main_menu;
+mod pause;
+mod score;
+mod ui;
 
-pub use game_state::GameState;
\ No newline at end of file
+use bevy::{app::{Plugin, Update}, prelude::AppExtStates};
+
+pub use game_state::{GameState, MenuScreen, PauseState};
+pub use level_progression::{LevelCleared, LevelProgression};
+pub use pause::Gameplay;
+
+pub struct GameFlowPlugin;
+impl Plugin for GameFlowPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .init_state::<GameState>()
+            .add_sub_state::<PauseState>()
+            .add_sub_state::<MenuScreen>()
+            .enable_state_scoped_entities::<GameState>()
+            .enable_state_scoped_entities::<PauseState>()
+            .enable_state_scoped_entities::<MenuScreen>()
+            .add_plugins((
+                loading::LoadingPlugin,
+                end_screen::EndScreenPlugin,
+                gem_hud::GemHudPlugin,
+                health_hud::HealthHudPlugin,
+                level_progression::LevelProgressionPlugin,
+                level_streaming::LevelStreamingPlugin,
+                main_menu::MainMenuPlugin,
+                pause::PausePlugin,
+                score::ScorePlugin,
+            ))
+            .add_systems(Update, ui::button_feedback);
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::{
+    app::{
+        Plugin,
+        Update
+    },
+    input::{
+        gamepad::{
+            GamepadButton,
+            GamepadButtonType,
+            Gamepads
+        },
+        ButtonInput
+    },
+    prelude::{
+        in_state,
+        BuildChildren,
+        Changed,
+        Commands,
+        Component,
+        IntoSystemConfigs,
+        IntoSystemSetConfigs,
+        KeyCode,
+        NextState,
+        OnEnter,
+        OnExit,
+        Query,
+        Res,
+        ResMut,
+        State,
+        StateScoped,
+        SystemSet
+    },
+    time::{
+        Time,
+        Virtual
+    },
+    ui::Interaction
+};
+use bevy_rapier2d::plugin::RapierConfiguration;
+
+use crate::font_handing::FontHandles;
+
+use super::{ui::{menu_root, spawn_button, spawn_title}, GameState, PauseState};
+
+/// Systems that simulate the game world, these only run while playing and not paused.
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Gameplay;
+
+pub struct PausePlugin;
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .configure_sets(Update, Gameplay.run_if(in_state(PauseState::Running)))
+            .add_systems(Update, toggle_pause.run_if(in_state(GameState::Playing)))
+            .add_systems(Update, pause_menu_actions.run_if(in_state(PauseState::Paused)))
+            .add_systems(OnEnter(PauseState::Paused), (freeze_world, spawn_pause_menu))
+            .add_systems(OnExit(PauseState::Paused), unfreeze_world);
+    }
+}
+
+fn toggle_pause(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    state: Res<State<PauseState>>,
+    mut next_state: ResMut<NextState<PauseState>>,
+) {
+    let start_pressed = gamepads
+        .iter()
+        .any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Start)));
+
+    if !(keyboard.just_pressed(KeyCode::Escape) || start_pressed) { return; }
+    next_state.set(match state.get() {
+        PauseState::Running => PauseState::Paused,
+        PauseState::Paused => PauseState::Running,
+    });
+}
+
+fn freeze_world(
+    mut time: ResMut<Time<Virtual>>,
+    mut physics: ResMut<RapierConfiguration>,
+) {
+    time.pause();
+    physics.physics_pipeline_active = false;
+}
+
+fn unfreeze_world(
+    mut time: ResMut<Time<Virtual>>,
+    mut physics: ResMut<RapierConfiguration>,
+) {
+    time.unpause();
+    physics.physics_pipeline_active = true;
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+enum PauseAction {
+    Resume,
+    MainMenu,
+}
+
+fn spawn_pause_menu(
+    mut commands: Commands,
+    fonts: Res<FontHandles>,
+) {
+    commands
+        .spawn((StateScoped(PauseState::Paused), menu_root()))
+        .with_children(|root| {
+            spawn_title(root, "Paused", fonts.default_font());
+            spawn_button(root, "Resume", fonts.default_font(), PauseAction::Resume);
+            spawn_button(root, "Main menu", fonts.default_font(), PauseAction::MainMenu);
+        });
+}
+
+fn pause_menu_actions(
+    buttons: Query<(&Interaction, &PauseAction), Changed<Interaction>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, action) in buttons.iter() {
+        if *interaction != Interaction::Pressed { continue; }
+        match action {
+            PauseAction::Resume => next_pause_state.set(PauseState::Running),
+            PauseAction::MainMenu => next_game_state.set(GameState::MainMenu),
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use std::time::Duration;
+
+use bevy::{
+    app::{
+        Plugin,
+        Update
+    },
+    prelude::{
+        resource_changed,
+        resource_exists,
+        Commands,
+        Condition,
+        Component,
+        Entity,
+        IntoSystemConfigs,
+        OnEnter,
+        OnExit,
+        Query,
+        Res,
+        ResMut,
+        Resource,
+        StateScoped,
+        TextBundle,
+        Trigger,
+        With
+    },
+    text::{
+        Text,
+        TextStyle
+    },
+    time::{
+        Time,
+        Timer,
+        TimerMode
+    },
+    ui::{
+        PositionType,
+        Style,
+        Val
+    },
+    utils::{
+        default,
+        HashSet
+    }
+};
+use bevy_rapier2d::prelude::KinematicCharacterControllerOutput;
+
+use crate::{
+    character::{Lives, PlayerTag},
+    enemies::{Defeated, Stomped},
+    font_handing::FontHandles
+};
+
+use super::{GameState, Gameplay};
+
+const POINTS_PER_ENEMY: u32 = 100;
+/// The character still stands on the enemy right after stomping it, landing only ends the combo after this.
+const COMBO_GRACE: Duration = Duration::from_millis(150);
+
+pub struct ScorePlugin;
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .init_resource::<Score>()
+            // a new run starts whenever a level is started from the main menu
+            .add_systems(OnExit(GameState::MainMenu), reset_score)
+            .add_systems(OnEnter(GameState::Playing), spawn_score_hud)
+            .add_systems(Update, end_combo_on_landing.in_set(Gameplay))
+            .add_systems(Update, update_score_hud.run_if(resource_exists::<Lives>.and_then(resource_changed::<Score>.or_else(resource_changed::<Lives>))))
+            .observe(count_stomp)
+            .observe(score_defeat);
+    }
+}
+
+/// Points for defeated enemies, stomping several enemies without landing multiplies them.
+/// Enemies defeated in any other way are always worth the base points.
+///
+/// This resource persists between levels, so it is only reset when a new game is started.
+#[derive(Resource, Debug)]
+pub struct Score {
+    points: u32,
+    combo: u32,
+    grace: Timer,
+    /// Enemies stomped during the combo that aren't defeated yet.
+    stomped: HashSet<Entity>,
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Self { points: 0, combo: 0, grace: Timer::new(COMBO_GRACE, TimerMode::Once), stomped: HashSet::new() }
+    }
+}
+
+impl Score {
+    pub fn points(&self) -> u32 { self.points }
+    /// Multiplier for the next defeated enemy.
+    pub fn multiplier(&self) -> u32 { self.combo.max(1) }
+}
+
+#[derive(Component, Debug)]
+struct ScoreText;
+
+fn reset_score(mut commands: Commands) {
+    commands.insert_resource(Score::default());
+}
+
+fn count_stomp(trigger: Trigger<Stomped>, mut score: ResMut<Score>) {
+    score.combo += 1;
+    score.grace.reset();
+    score.stomped.insert(trigger.event().enemy);
+}
+
+fn score_defeat(trigger: Trigger<Defeated>, mut score: ResMut<Score>) {
+    let multiplier = if score.stomped.remove(&trigger.entity()) { score.multiplier() } else { 1 };
+    score.points += POINTS_PER_ENEMY * multiplier;
+}
+
+fn end_combo_on_landing(
+    time: Res<Time>,
+    mut score: ResMut<Score>,
+    player: Query<&KinematicCharacterControllerOutput, With<PlayerTag>>,
+) {
+    if score.combo == 0 { return; }
+    // ticking changes the score, so it only happens while there is a combo
+    if !score.grace.tick(time.delta()).finished() { return; }
+    if player.iter().any(|output| output.grounded) {
+        score.combo = 0;
+        score.stomped.clear();
+    }
+}
+
+fn spawn_score_hud(
+    mut commands: Commands,
+    fonts: Res<FontHandles>,
+    score: Res<Score>,
+    lives: Res<Lives>,
+) {
+    commands.spawn((
+        StateScoped(GameState::Playing),
+        ScoreText,
+        TextBundle::from_section(score_text(&score, &lives), TextStyle { font: fonts.default_font(), font_size: 24.0, ..default() })
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(8.0),
+                right: Val::Px(12.0),
+                ..default()
+            }),
+    ));
+}
+
+fn update_score_hud(score: Res<Score>, lives: Res<Lives>, mut texts: Query<&mut Text, With<ScoreText>>) {
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = score_text(&score, &lives);
+    }
+}
+
+fn score_text(score: &Score, lives: &Lives) -> String {
+    if score.combo > 1 {
+        format!("Lives: {}  Score: {}  x{}", lives.remaining(), score.points(), score.multiplier())
+    } else {
+        format!("Lives: {}  Score: {}", lives.remaining(), score.points())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::App;
+
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app
+            .init_resource::<Score>()
+            .observe(count_stomp)
+            .observe(score_defeat);
+        app
+    }
+
+    #[test]
+    fn stomps_without_landing_multiply_the_points() {
+        let mut app = app();
+        let world = app.world_mut();
+        let (player, first, second) = (world.spawn_empty().id(), world.spawn_empty().id(), world.spawn_empty().id());
+
+        world.trigger_targets(Stomped { enemy: first }, player);
+        world.trigger_targets(Defeated, first);
+        world.trigger_targets(Stomped { enemy: second }, player);
+        world.trigger_targets(Defeated, second);
+
+        assert_eq!(world.resource::<Score>().points(), POINTS_PER_ENEMY + 2 * POINTS_PER_ENEMY);
+    }
+
+    #[test]
+    fn other_kills_during_a_combo_are_not_multiplied() {
+        let mut app = app();
+        let world = app.world_mut();
+        let (player, first, second, shot) = (world.spawn_empty().id(), world.spawn_empty().id(), world.spawn_empty().id(), world.spawn_empty().id());
+
+        world.trigger_targets(Stomped { enemy: first }, player);
+        world.trigger_targets(Stomped { enemy: second }, player);
+        world.trigger_targets(Defeated, shot);
+
+        assert_eq!(world.resource::<Score>().points(), POINTS_PER_ENEMY);
+    }
+}
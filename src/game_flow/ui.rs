@@ -0,0 +1,87 @@
+use bevy::{
+    asset::Handle,
+    color::Color,
+    prelude::{
+        BuildChildren,
+        Button,
+        ButtonBundle,
+        ChildBuilder,
+        Changed,
+        Component,
+        NodeBundle,
+        Query,
+        TextBundle,
+        With
+    },
+    text::{
+        Font,
+        TextStyle
+    },
+    ui::{
+        AlignItems,
+        BackgroundColor,
+        FlexDirection,
+        Interaction,
+        JustifyContent,
+        Style,
+        UiRect,
+        Val
+    },
+    utils::default
+};
+
+const BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
+const BUTTON_COLOR_HOVERED: Color = Color::srgb(0.25, 0.25, 0.25);
+const BUTTON_COLOR_PRESSED: Color = Color::srgb(0.35, 0.55, 0.35);
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+/// Full screen column that centers its children, used as the root of every menu.
+pub fn menu_root() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        background_color: OVERLAY_COLOR.into(),
+        ..default()
+    }
+}
+
+pub fn spawn_title(parent: &mut ChildBuilder, title: &str, font: Handle<Font>) {
+    parent.spawn(TextBundle::from_section(title, TextStyle { font, font_size: 60.0, ..default() }));
+}
+
+pub fn spawn_button(parent: &mut ChildBuilder, label: &str, font: Handle<Font>, action: impl Component) {
+    parent
+        .spawn((
+            action,
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(260.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: BUTTON_COLOR.into(),
+                ..default()
+            },
+        ))
+        .with_children(|button| {
+            button.spawn(TextBundle::from_section(label, TextStyle { font, font_size: 30.0, ..default() }));
+        });
+}
+
+pub fn button_feedback(mut buttons: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>) {
+    for (interaction, mut color) in buttons.iter_mut() {
+        color.0 = match interaction {
+            Interaction::Pressed => BUTTON_COLOR_PRESSED,
+            Interaction::Hovered => BUTTON_COLOR_HOVERED,
+            Interaction::None => BUTTON_COLOR,
+        };
+    }
+}
@@ -0,0 +1,100 @@
+use bevy::{
+    ecs::{component::ComponentId, world::DeferredWorld},
+    prelude::{BuildChildren, Bundle, Commands, Component, Entity, Parent, Query, Res, SpatialBundle, Transform, Trigger},
+    sprite::{SpriteBundle, TextureAtlas},
+    utils::default
+};
+use bevy_ecs_ldtk::{prelude::LdtkFields, EntityInstance, LdtkEntity};
+use bevy_rapier2d::{plugin::RapierContext, prelude::{Collider, CollisionGroups, Group}};
+
+use crate::{character::{ImageHandles, Interact}, collision::LocalGroupNames, enemies::{ColliderBundle, ObservableColliderBundle}, unsorted::{Promise, PromiseProcedure}};
+
+use super::{pickup::eject_pickups, Item};
+
+#[derive(Default, Bundle, LdtkEntity)]
+pub(super) struct Chest {
+    promise: Promise<Self>,
+
+    #[with(retrieve_content)]
+    content: ChestContent,
+}
+
+/// Items ejected when the chest is opened, set with the `content` field in LDtk.
+#[derive(Component, Default, Clone, Debug)]
+pub(super) struct ChestContent(Vec<Item>);
+
+fn retrieve_content(entity_instance: &EntityInstance) -> ChestContent {
+    ChestContent(
+        entity_instance
+            .get_maybe_enums_field("content")
+            .map(|values| Item::from_ldtk_values(values).collect())
+            .unwrap_or_default()
+    )
+}
+
+#[derive(Component, Debug)]
+pub(super) struct ChestState {
+    sprite: Entity,
+    opened: bool,
+}
+
+const TILESIZE: f32 = 16.0;
+// placeholders: the atlas has no chest, the stump is used as the closed chest and the hollow log as the opened one
+const CLOSED_TILE: usize = 193;
+const OPENED_TILE: usize = 194;
+
+impl PromiseProcedure for Chest {
+    fn resolve_promise(mut world: DeferredWorld, entity: Entity, component_id: ComponentId) {
+        let images = world.resource::<ImageHandles>();
+        let (texture, layout) = (images.atlas.clone_weak(), images.atlas_layout.clone_weak());
+        let mut sprite = Entity::PLACEHOLDER;
+        world
+            .commands()
+            .entity(entity)
+            .with_children(|children| {
+                sprite = children.spawn((
+                    SpriteBundle { texture, ..default() },
+                    TextureAtlas { layout, index: CLOSED_TILE },
+                )).id();
+                children.spawn((
+                    SpatialBundle::default(),
+                    ObservableColliderBundle::from(ColliderBundle {
+                        collider: Collider::cuboid(TILESIZE / 2.0, TILESIZE / 2.0),
+                        collision_groups: CollisionGroups {
+                            memberships: Group::CHEST,
+                            filters: Group::PLAYER,
+                        },
+                    }),
+                ));
+            })
+            .insert(ChestState { sprite, opened: false })
+            .remove_by_id(component_id);
+    }
+}
+
+/// Opens the chest the interacting character stands at.
+pub(super) fn open_chest(
+    trigger: Trigger<Interact>,
+    mut commands: Commands,
+    rapier: Res<RapierContext>,
+    sensors: Query<&Parent>,
+    mut chests: Query<(&mut ChestState, &mut ChestContent, &Transform, Option<&Parent>)>,
+    mut sprites: Query<&mut TextureAtlas>,
+) {
+    let character = trigger.event().character;
+    let chest = rapier
+        .intersection_pairs_with(character)
+        .filter(|(_, _, intersecting)| *intersecting)
+        .map(|(first, second, _)| if first == character { second } else { first })
+        .filter_map(|sensor| sensors.get(sensor).map(Parent::get).ok())
+        .find(|chest| chests.get(*chest).is_ok_and(|(state, ..)| !state.opened));
+    let Some(chest) = chest else { return; };
+    let Ok((mut state, mut content, transform, parent)) = chests.get_mut(chest) else { return; };
+    state.opened = true;
+
+    if let Ok(mut atlas) = sprites.get_mut(state.sprite) {
+        atlas.index = OPENED_TILE;
+    }
+
+    eject_pickups(&mut commands, std::mem::take(&mut content.0), *transform, parent);
+}
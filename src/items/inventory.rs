@@ -0,0 +1,81 @@
+use bevy::{
+    prelude::{
+        Changed,
+        Commands,
+        Component,
+        Entity,
+        Event,
+        Query,
+        Trigger
+    },
+    utils::HashMap
+};
+use bevy_ecs_ldtk::{prelude::LdtkFields, EntityInstance};
+
+use crate::character::{set_health, Health, MovementBoost};
+
+use super::Item;
+
+const BOOTS_BOOST: MovementBoost = MovementBoost { speed: 1.25, jump: 1.2 };
+
+#[derive(Component, Default, Debug, Clone)]
+pub struct Inventory {
+    items: HashMap<Item, u32>,
+}
+
+impl Inventory {
+    /// Reads an `Array<LocalEnum.Item>` field of an LDtk entity, like `Player.items`.
+    pub fn from_ldtk_field(entity_instance: &EntityInstance, field: &str) -> Self {
+        let mut inventory = Self::default();
+        if let Ok(values) = entity_instance.get_maybe_enums_field(field) {
+            Item::from_ldtk_values(values).for_each(|item| inventory.add(item));
+        }
+        inventory
+    }
+
+    pub fn add(&mut self, item: Item) { *self.items.entry(item).or_default() += 1; }
+    pub fn count(&self, item: Item) -> u32 { self.items.get(&item).copied().unwrap_or_default() }
+    pub fn contains(&self, item: Item) -> bool { self.count(item) > 0 }
+    pub fn gems(&self) -> u32 { self.count(Item::Gem) }
+
+    /// Removes one of the item, returns false when there was none to take.
+    pub fn take(&mut self, item: Item) -> bool {
+        let Some(count) = self.items.get_mut(&item) else { return false; };
+        *count -= 1;
+        if *count == 0 { self.items.remove(&item); }
+        true
+    }
+}
+
+/// Triggered on the character that picked up an item.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ItemCollected(pub Item);
+
+pub(super) fn collect_item(
+    trigger: Trigger<ItemCollected>,
+    mut commands: Commands,
+    mut characters: Query<(&mut Inventory, Option<&mut Health>)>,
+) {
+    let entity = trigger.entity();
+    let ItemCollected(item) = *trigger.event();
+    let Ok((mut inventory, health)) = characters.get_mut(entity) else { return; };
+
+    match (item.healing(), health) {
+        (Some(healing), Some(mut health)) => {
+            let current = health.current() + healing;
+            set_health(&mut commands, entity, &mut health, current);
+        },
+        _ => inventory.add(item),
+    }
+}
+
+/// Passive item effects, applied whenever the inventory changes so the starting items count as well.
+pub(super) fn apply_passive_items(
+    mut commands: Commands,
+    characters: Query<(Entity, &Inventory), Changed<Inventory>>,
+) {
+    for (entity, inventory) in characters.iter() {
+        let boost = if inventory.contains(Item::Boots) { BOOTS_BOOST } else { MovementBoost::default() };
+        commands.entity(entity).insert(boost);
+    }
+}
@@ -0,0 +1,39 @@
+use bevy::{color::Color, log::warn};
+
+/// Generated from the `Item` enum of the LDtk project, the variants are matched by their LDtk identifier.
+pub use crate::unsorted::enums::Item;
+
+impl Item {
+    /// Health restored when the item is picked up, consumables aren't kept in the inventory.
+    pub const fn healing(&self) -> Option<u32> {
+        match self {
+            Self::HealingPlant => Some(1),
+            Self::Meat => Some(2),
+            _ => None,
+        }
+    }
+
+    /// There are no item sprites in the atlas yet, so items are drawn as a colored square.
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Knife => Color::srgb(0.75, 0.75, 0.8),
+            Self::HealingPlant => Color::srgb(0.3, 0.8, 0.3),
+            Self::Meat => Color::srgb(0.7, 0.25, 0.2),
+            Self::Boots => Color::srgb(0.45, 0.3, 0.15),
+            Self::Water => Color::srgb(0.3, 0.5, 0.9),
+            Self::Gem => Color::srgb(0.9, 0.2, 0.8),
+            Self::Key => Color::srgb(0.95, 0.8, 0.2),
+        }
+    }
+
+    /// Parses the values of an LDtk `Array<LocalEnum.Item>` field, unknown and empty values are skipped.
+    pub fn from_ldtk_values(values: &[Option<String>]) -> impl Iterator<Item = Item> + '_ {
+        values
+            .iter()
+            .flatten()
+            .filter_map(|value| value.parse()
+                .inspect_err(|_| warn!("Unknown item {:?} in LDtk field!", value))
+                .ok()
+            )
+    }
+}
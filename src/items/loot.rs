@@ -0,0 +1,104 @@
+use bevy::{
+    asset::{io::Reader, Asset, AssetLoader, AssetServer, Assets, AsyncReadExt, Handle, LoadContext, LoadState},
+    prelude::{Commands, Component, Parent, Query, Res, Resource, Transform, Trigger},
+    reflect::TypePath,
+    utils::HashMap
+};
+use bevy_ecs_ldtk::{prelude::LdtkFields, EntityInstance};
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::enemies::Defeated;
+
+use super::{pickup::eject_pickups, Item};
+
+const DROP_TABLES_PATH: &str = "drop_tables.drops.json";
+
+/// Items always dropped when the enemy is defeated, set with the `loot` field in LDtk.
+#[derive(Component, Default, Clone, Debug)]
+pub struct Loot(Vec<Item>);
+
+impl Loot {
+    pub fn from_ldtk_field(entity_instance: &EntityInstance, field: &str) -> Self {
+        Self(
+            entity_instance
+                .get_maybe_enums_field(field)
+                .map(|values| Item::from_ldtk_values(values).collect())
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// A possible drop, `chance` is rolled once and between `min` and `max` of the item are dropped.
+#[derive(Deserialize, Debug, Clone)]
+struct LootDrop {
+    item: Item,
+    chance: f32,
+    #[serde(default = "one")] min: u32,
+    #[serde(default = "one")] max: u32,
+}
+
+fn one() -> u32 { 1 }
+
+/// Extra random drops per LDtk entity identifier, read from `assets/drop_tables.drops.json`.
+#[derive(Asset, TypePath, Deserialize, Default, Debug)]
+pub(super) struct DropTables(HashMap<String, Vec<LootDrop>>);
+
+#[derive(Default)]
+pub(super) struct DropTablesLoader;
+
+impl AssetLoader for DropTablesLoader {
+    type Asset = DropTables;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut json = Vec::new();
+        reader.read_to_end(&mut json).await?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    // a plain "json" extension would claim every json asset
+    fn extensions(&self) -> &[&str] { &["drops.json"] }
+}
+
+#[derive(Resource)]
+pub struct DropTablesHandle(Handle<DropTables>);
+
+impl DropTablesHandle {
+    /// Enemies only drop their [Loot] when the drop tables fail to load, so a failed load counts as done.
+    pub fn is_loaded(&self, server: &AssetServer) -> bool {
+        matches!(server.load_state(&self.0), LoadState::Loaded | LoadState::Failed(_))
+    }
+}
+
+pub(super) fn load_drop_tables(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(DropTablesHandle(asset_server.load(DROP_TABLES_PATH)));
+}
+
+pub(super) fn drop_loot(
+    trigger: Trigger<Defeated>,
+    mut commands: Commands,
+    enemies: Query<(&Transform, Option<&Parent>, Option<&Loot>, Option<&EntityInstance>)>,
+    tables_handle: Res<DropTablesHandle>,
+    tables: Res<Assets<DropTables>>,
+) {
+    let Ok((transform, parent, loot, entity_instance)) = enemies.get(trigger.entity()) else { return; };
+    let mut items = loot.map(|loot| loot.0.clone()).unwrap_or_default();
+
+    let tables = tables.get(&tables_handle.0);
+    let drops = entity_instance.zip(tables).and_then(|(instance, tables)| tables.0.get(&instance.identifier));
+    let mut rng = rand::thread_rng();
+    for drop in drops.into_iter().flatten() {
+        if !rng.gen_bool(drop.chance.clamp(0.0, 1.0) as f64) { continue; }
+        let quantity = rng.gen_range(drop.min..=drop.max.max(drop.min));
+        items.extend(std::iter::repeat_n(drop.item, quantity as usize));
+    }
+
+    eject_pickups(&mut commands, items, *transform, parent);
+}
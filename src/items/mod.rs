@@ -0,0 +1,29 @@
+mod chest;
+mod inventory;
+mod item;
+mod loot;
+mod pickup;
+
+use bevy::{app::{Plugin, Startup, Update}, asset::AssetApp, prelude::IntoSystemConfigs};
+
+use crate::{game_flow::Gameplay, unsorted::{entities, BevyPromiseResolver}};
+
+pub use inventory::{Inventory, ItemCollected};
+pub use item::Item;
+pub use loot::{DropTablesHandle, Loot};
+
+pub struct ItemPlugin;
+impl Plugin for ItemPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app
+            .register_ldtk_entity_with_promise::<pickup::Pickup>(entities::PICKUP)
+            .register_ldtk_entity_with_promise::<chest::Chest>(entities::CHEST)
+            .observe(chest::open_chest)
+            .observe(inventory::collect_item)
+            .observe(loot::drop_loot)
+            .init_asset::<loot::DropTables>()
+            .init_asset_loader::<loot::DropTablesLoader>()
+            .add_systems(Startup, loot::load_drop_tables)
+            .add_systems(Update, inventory::apply_passive_items.in_set(Gameplay));
+    }
+}
@@ -0,0 +1,123 @@
+use std::slice;
+
+use bevy::{
+    ecs::{component::ComponentId, world::DeferredWorld},
+    math::Vec2,
+    prelude::{BuildChildren, Bundle, Commands, Component, DespawnRecursiveExt, Entity, Parent, Query, SpatialBundle, Transform, Trigger, With},
+    sprite::{Sprite, SpriteBundle},
+    utils::default
+};
+use bevy_ecs_ldtk::{prelude::LdtkFields, EntityInstance, LdtkEntity};
+use bevy_rapier2d::prelude::{Collider, CollisionEvent, CollisionGroups, Group, LockedAxes, RigidBody, Velocity};
+
+use crate::{character::PlayerTag, collision::LocalGroupNames, enemies::{ColliderBundle, ObservableColliderBundle}, unsorted::{Promise, PromiseProcedure}};
+
+use super::{Item, ItemCollected};
+
+#[derive(Default, Bundle, LdtkEntity)]
+pub(super) struct Pickup {
+    promise: Promise<Self>,
+
+    #[with(retrieve_item)]
+    item: PickupItem,
+}
+
+/// The item a pickup gives, set with the `item` field in LDtk.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PickupItem(pub Item);
+
+impl Default for PickupItem {
+    fn default() -> Self { Self(Item::Gem) }
+}
+
+fn retrieve_item(entity_instance: &EntityInstance) -> PickupItem {
+    entity_instance
+        .get_maybe_enum_field("item")
+        .ok()
+        .and_then(|value| Item::from_ldtk_values(slice::from_ref(value)).next())
+        .map(PickupItem)
+        .unwrap_or_default()
+}
+
+const PICKUP_SIZE: f32 = 8.0;
+
+impl PromiseProcedure for Pickup {
+    fn resolve_promise(mut world: DeferredWorld, entity: Entity, component_id: ComponentId) {
+        let PickupItem(item) = *world.get::<PickupItem>(entity).expect("Pickup bundle contains the item!");
+        world
+            .commands()
+            .entity(entity)
+            .with_children(|children| {
+                children.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: item.color(),
+                        custom_size: Some(Vec2::splat(PICKUP_SIZE)),
+                        ..default()
+                    },
+                    ..default()
+                });
+                children.spawn((
+                    SpatialBundle::default(),
+                    ObservableColliderBundle::from(ColliderBundle {
+                        collider: Collider::cuboid(PICKUP_SIZE / 2.0, PICKUP_SIZE / 2.0),
+                        collision_groups: CollisionGroups {
+                            memberships: Group::PICKUP,
+                            filters: Group::PLAYER,
+                        },
+                    }),
+                ))
+                .observe(pick_up);
+            })
+            .remove_by_id(component_id);
+    }
+}
+
+/// A pickup that falls and collides with the level, like the items ejected from a chest.
+fn physical_pickup(item: Item, transform: Transform, velocity: Vec2) -> impl Bundle {
+    (
+        Pickup { item: PickupItem(item), ..default() },
+        SpatialBundle::from_transform(transform),
+        RigidBody::Dynamic,
+        LockedAxes::ROTATION_LOCKED,
+        Velocity::linear(velocity),
+        Collider::cuboid(PICKUP_SIZE / 2.0, PICKUP_SIZE / 2.0),
+        // only collides with the level, the player collects it through the sensor
+        CollisionGroups {
+            memberships: Group::PICKUP,
+            filters: Group::ALL & !(Group::PLAYER | Group::TEST_ENEMY | Group::PICKUP),
+        },
+    )
+}
+
+const EJECT_VELOCITY: Vec2 = Vec2::new(40.0, 120.0);
+
+/// Throws the items out of `transform` as physical pickups, spread out sideways.
+/// They are added to `parent` (the layer of the chest or enemy dropping them) so they are despawned together with its level.
+pub fn eject_pickups(commands: &mut Commands, items: Vec<Item>, transform: Transform, parent: Option<&Parent>) {
+    let spread = (items.len() as f32 - 1.0) / 2.0;
+    for (index, item) in items.into_iter().enumerate() {
+        let velocity = EJECT_VELOCITY * Vec2::new(index as f32 - spread, 1.0);
+        let pickup = commands.spawn(physical_pickup(item, transform, velocity)).id();
+        if let Some(parent) = parent {
+            commands.entity(parent.get()).add_child(pickup);
+        }
+    }
+}
+
+fn pick_up(
+    trigger: Trigger<CollisionEvent>,
+    mut commands: Commands,
+    sensors: Query<&Parent>,
+    pickups: Query<&PickupItem>,
+    player: Query<(), With<PlayerTag>>,
+) {
+    let CollisionEvent::Started(first, second, _) = *trigger.event() else { return; };
+    let other = if first == trigger.entity() { second } else { first };
+    if !player.contains(other) { return; }
+
+    let Ok(pickup) = sensors.get(trigger.entity()).map(Parent::get) else { return; };
+    // the player can touch the pickup again before it is despawned, it is only picked up once
+    let Ok(PickupItem(item)) = pickups.get(pickup).copied() else { return; };
+    commands.entity(pickup).despawn_recursive();
+    commands.trigger_targets(ItemCollected(item), other);
+}
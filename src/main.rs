@@ -5,16 +5,21 @@ mod font_handing;
 mod enemies;
 mod collision;
 mod finish;
+mod checkpoint;
+mod door;
 mod player;
 mod custom_character_controller;
 mod character;
+mod camera;
+mod items;
+mod projectile;
 
-use bevy::{app::{App, PreStartup, Startup, Update}, asset::AssetServer, math::{Vec2, Vec3}, prelude::{AppExtStates, Camera, Camera2dBundle, Commands, OnEnter, OnExit, Query, Res, ResMut, Transform, With}, utils::default, DefaultPlugins};
-use bevy_ecs_ldtk::{LdtkPlugin, LdtkWorldBundle, LevelSelection};
+use bevy::{app::{App, PreStartup, Update}, math::Vec2, prelude::{IntoSystemConfigs, OnEnter, OnExit, Res, ResMut}, DefaultPlugins};
+use bevy_ecs_ldtk::{LdtkPlugin, LevelSelection};
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use bevy_rapier2d::{plugin::{NoUserData, RapierConfiguration, RapierPhysicsPlugin}, render::RapierDebugRenderPlugin};
-use font_handing::{FontHandles, FontPlugin};
-use game_flow::GameState;
+use font_handing::FontPlugin;
+use game_flow::{GameFlowPlugin, GameState, Gameplay};
 use player::PlayerPlugin;
 use character::CharacterPlugin;
 use unsorted::LDTKEnumTagPluginCustom;
@@ -27,7 +32,7 @@ pub fn main() {
     
     app
         .add_plugins(DefaultPlugins)
-        .init_state::<GameState>()         
+        .add_plugins(GameFlowPlugin)
 
         .add_plugins(LdtkPlugin)
         .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(16.))
@@ -38,19 +43,23 @@ pub fn main() {
 
         .add_systems(PreStartup, |mut physics: ResMut<RapierConfiguration>| physics.gravity = Vec2::NEG_Y * 275.0)
         
-        .add_systems(Startup, setup)
+        .add_systems(PreStartup, ldtk_level_handler::load_world)
+        .add_plugins(camera::CameraPlugin)
         .insert_resource(LevelSelection::index(0))
+        .add_systems(OnEnter(GameState::Playing), ldtk_level_handler::spawn_world)
         
         .add_plugins(FontPlugin)
-        .add_systems(OnEnter(GameState::Defeated), spawn_defeat_text)
-        .add_systems(OnEnter(GameState::Completed), spawn_complete_text)
         
         .add_plugins(enemies::EnemyPlugin)
 
         .add_plugins(finish::FinishPlugin)
-
-        // temp
-        .add_systems(Update, kill_or_complete_on_keypress)
+        .add_plugins(checkpoint::CheckpointPlugin)
+        .add_plugins(items::ItemPlugin)
+        .add_plugins(door::DoorPlugin)
+        .add_plugins(projectile::ProjectilePlugin)
+        .add_plugins(collision::CollisionHanlerPlugin)
+        .add_plugins(collision::LevelColliderPlugin)
+        .add_plugins(collision::OneWayPlatformPlugin)
 
         .add_systems(OnExit(GameState::Playing), ldtk_level_handler::despawn_worlds) 
         ;
@@ -59,26 +68,16 @@ pub fn main() {
     app
         .add_plugins(WorldInspectorPlugin::default())
         .add_plugins(RapierDebugRenderPlugin::default())
-        .add_plugins(debug::DebugPlugin);
+        .add_plugins(debug::DebugPlugin)
+        // shortcuts to the end screens
+        .add_systems(Update, kill_or_complete_on_keypress.in_set(Gameplay));
         
     app.run();
 }
 
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let mut camera = Camera2dBundle::default();
-    camera.projection.scale = 0.5;
-    camera.transform.translation.x += 1280.0 / 4.0 + 100.;
-    camera.transform.translation.y += 720.0 / 4.0;
-    commands.spawn(camera);
-
-    commands.spawn(LdtkWorldBundle {
-        ldtk_handle: asset_server.load("game.ldtk"),
-        ..Default::default()
-    });
-}
-
 // temp test junk
+#[cfg(feature = "debug")]
 fn kill_or_complete_on_keypress(
     input: Res<bevy::input::ButtonInput<bevy::prelude::KeyCode>>,
     mut game_state: ResMut<bevy::prelude::NextState<GameState>>  
@@ -90,51 +89,3 @@ fn kill_or_complete_on_keypress(
         game_state.set(GameState::Completed);
     }
 }
-
-use bevy::text::{
-    Text2dBundle,
-    Text,
-    TextStyle,
-};
-
-fn spawn_defeat_text(
-    mut commands: Commands,
-    fonts: Res<FontHandles>,
-    mut camera: Query<&mut Transform, With<Camera>>,
-) {
-    let text_style = TextStyle {
-        font: fonts.default_font(),
-        font_size: 60.0,
-        ..default()
-    };
-
-    let text = Text2dBundle {
-        text: Text::from_section("You died!", text_style.clone()),
-        ..default()
-    };
-
-    camera.single_mut().translation = Vec3::ZERO;
-
-    commands.spawn(text);
-}
-
-fn spawn_complete_text(
-    mut commands: Commands,
-    fonts: Res<FontHandles>,
-    mut camera: Query<&mut Transform, With<Camera>>,
-) {
-    let text_style = TextStyle {
-        font: fonts.default_font(),
-        font_size: 60.0,
-        ..default()
-    };
-
-    let text = Text2dBundle {
-        text: Text::from_section("You win!", text_style.clone()),
-        ..default()
-    };
-
-    camera.single_mut().translation = Vec3::ZERO;
-
-    commands.spawn(text);
-}
\ No newline at end of file
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Plugin, Update},
+    math::Vec2,
+    prelude::{Bundle, Commands, Component, DespawnRecursiveExt, Entity, Event, IntoSystemConfigs, OnExit, Or, Query, Res, Transform, Trigger, With},
+    sprite::SpriteBundle,
+    time::{Time, Timer, TimerMode}
+};
+use bevy_rapier2d::prelude::{Collider, CollisionEvent, CollisionGroups, Group, Sensor};
+
+use crate::{
+    character::{Damage, PlayerTag},
+    collision::{LocalGroupNames, OneWayPlatform},
+    enemies::{ColliderBundle, ObservableColliderBundle},
+    game_flow::{GameState, Gameplay}
+};
+
+/// Moves projectiles, despawns them when they hit something or their lifetime runs out and hurts the player they hit.
+pub struct ProjectilePlugin;
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .observe(hit_on_overlap)
+            .observe(hurt_player)
+            .add_systems(Update, (move_projectiles, expire_projectiles).in_set(Gameplay))
+            // projectiles aren't part of a level, so they aren't despawned with the world
+            .add_systems(OnExit(GameState::Playing), despawn_projectiles);
+    }
+}
+
+const KNOCKBACK_VELOCITY: f32 = 150.0;
+
+#[derive(Component, Debug)]
+pub struct Projectile {
+    /// Pixels per second.
+    pub velocity: Vec2,
+    pub damage: u32,
+    lifetime: Timer,
+    /// A projectile can overlap several colliders in the same frame, it only hits the first.
+    spent: bool,
+}
+
+impl Projectile {
+    pub fn new(velocity: Vec2, damage: u32, lifetime: Duration) -> Self {
+        Self { velocity, damage, lifetime: Timer::new(lifetime, TimerMode::Once), spent: false }
+    }
+}
+
+/// Triggered on whatever a projectile hits, like [CharacterColision](crate::character::CharacterColision) for characters.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ProjectileHit {
+    pub damage: u32,
+    pub velocity: Vec2,
+}
+
+#[derive(Bundle)]
+pub struct ProjectileBundle {
+    projectile: Projectile,
+    sprite: SpriteBundle,
+    collider: ObservableColliderBundle,
+}
+
+impl ProjectileBundle {
+    /// `sprite` is placed where the projectile starts.
+    pub fn new(projectile: Projectile, sprite: SpriteBundle, radius: f32) -> Self {
+        Self {
+            projectile,
+            sprite,
+            collider: ObservableColliderBundle::from(ColliderBundle {
+                collider: Collider::ball(radius),
+                collision_groups: CollisionGroups {
+                    memberships: Group::PROJECTILE,
+                    // enemies don't hit themselves or each other, projectiles pass through one-way platforms even while the player stands on them
+                    filters: Group::ALL & !(Group::TEST_ENEMY | Group::TEST_ENEMY_SENSOR | Group::PROJECTILE | Group::PICKUP | Group::ONE_WAY_PLATFORM | Group::PLAYER_SOLID),
+                },
+            }),
+        }
+    }
+}
+
+fn move_projectiles(time: Res<Time>, mut projectiles: Query<(&Projectile, &mut Transform)>) {
+    for (projectile, mut transform) in projectiles.iter_mut() {
+        transform.translation += (projectile.velocity * time.delta_seconds()).extend(0.0);
+    }
+}
+
+fn expire_projectiles(mut commands: Commands, time: Res<Time>, mut projectiles: Query<(Entity, &mut Projectile)>) {
+    for (entity, mut projectile) in projectiles.iter_mut() {
+        if projectile.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn despawn_projectiles(mut commands: Commands, projectiles: Query<Entity, With<Projectile>>) {
+    for entity in projectiles.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Projectiles are sensors, they hit the player and the terrain alike and are gone afterwards.
+fn hit_on_overlap(
+    trigger: Trigger<CollisionEvent>,
+    mut commands: Commands,
+    mut projectiles: Query<&mut Projectile>,
+    // ladders, checkpoints and the like aren't in the way, neither are one-way platforms
+    pass_through: Query<(), Or<(With<Sensor>, With<OneWayPlatform>)>>,
+) {
+    let Ok(mut projectile) = projectiles.get_mut(trigger.entity()) else { return; };
+    let CollisionEvent::Started(first, second, _) = *trigger.event() else { return; };
+    let other = if first == trigger.entity() { second } else { first };
+    if projectile.spent || pass_through.contains(other) { return; }
+    projectile.spent = true;
+
+    commands.trigger_targets(ProjectileHit { damage: projectile.damage, velocity: projectile.velocity }, other);
+    commands.entity(trigger.entity()).despawn_recursive();
+}
+
+fn hurt_player(
+    trigger: Trigger<ProjectileHit>,
+    mut commands: Commands,
+    player: Query<(), With<PlayerTag>>,
+) {
+    if !player.contains(trigger.entity()) { return; }
+    let hit = trigger.event();
+    // pushed away from where the projectile came from
+    let knockback = (hit.velocity.normalize_or_zero() + Vec2::Y).normalize_or_zero() * KNOCKBACK_VELOCITY;
+    commands.trigger_targets(Damage { amount: hit.damage, knockback }, trigger.entity());
+}
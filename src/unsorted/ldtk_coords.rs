@@ -0,0 +1,48 @@
+use bevy::math::{IVec2, Rect, Vec2};
+use bevy_ecs_ldtk::{
+    ldtk::{EntityInstance, LdtkJson, Level, ReferenceToAnEntityInstance},
+    utils::{grid_coords_to_translation, ldtk_grid_coords_to_grid_coords}
+};
+
+/// Position of the bottom left corner of a level when levels are spawned with `LevelSpawnBehavior::UseWorldTranslation`.
+pub fn level_world_offset(level: &Level) -> Vec2 {
+    Vec2::new(level.world_x as f32, -(level.world_y + level.px_hei) as f32)
+}
+
+/// Area a level covers in bevy's world space.
+pub fn level_world_rect(level: &Level) -> Rect {
+    let offset = level_world_offset(level);
+    Rect::from_corners(offset, offset + Vec2::new(level.px_wid as f32, level.px_hei as f32))
+}
+
+/// Center of the LDtk grid cell `point` (y pointing down), relative to the level it is in.
+pub fn ldtk_grid_point_to_level_translation(point: IVec2, level_height_in_cells: i32, grid_size: i32) -> Vec2 {
+    grid_coords_to_translation(ldtk_grid_coords_to_grid_coords(point, level_height_in_cells), IVec2::splat(grid_size))
+}
+
+/// Center of an entity placed in `level`, in bevy's world space.
+pub fn entity_world_center(level: &Level, entity: &EntityInstance) -> Vec2 {
+    let size = Vec2::new(entity.width as f32, entity.height as f32);
+    let center = entity.px.as_vec2() + (Vec2::splat(0.5) - entity.pivot) * size;
+    level_world_offset(level) + Vec2::new(center.x, level.px_hei as f32 - center.y)
+}
+
+/// Center of the LDtk grid cell `point` (y pointing down) in `level`, in bevy's world space.
+/// Used for `Point` fields, which are relative to the level of the entity or the level they belong to.
+pub fn ldtk_point_to_world(level: &Level, point: IVec2, grid_size: i32) -> Vec2 {
+    level_world_offset(level) + ldtk_grid_point_to_level_translation(point, level.px_hei / grid_size, grid_size)
+}
+
+/// The level an entity is placed in and the entity itself, found by the iid of the entity.
+pub fn find_entity<'a>(project: &'a LdtkJson, entity_iid: &str) -> Option<(&'a Level, &'a EntityInstance)> {
+    project
+        .levels
+        .iter()
+        .flat_map(|level| level.layer_instances.iter().flatten().flat_map(move |layer| layer.entity_instances.iter().map(move |entity| (level, entity))))
+        .find(|(_, entity)| entity.iid == entity_iid)
+}
+
+/// Center of the entity an `EntityRef` field points to, in bevy's world space.
+pub fn entity_ref_world_center(project: &LdtkJson, entity_ref: &ReferenceToAnEntityInstance) -> Option<Vec2> {
+    find_entity(project, &entity_ref.entity_iid).map(|(level, entity)| entity_world_center(level, entity))
+}
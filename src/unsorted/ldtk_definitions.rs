@@ -0,0 +1,3 @@
+//! Enums, entity identifiers and IntGrid values of `assets/game.ldtk`, generated by `build.rs`.
+
+include!(concat!(env!("OUT_DIR"), "/ldtk_definitions.rs"));
@@ -1,15 +1,47 @@
-use bevy_ecs_ldtk::assets::LdtkProject;
+use bevy_ecs_ldtk::{assets::LdtkProject, LdtkWorldBundle, LevelIid};
 use bevy::{
-    asset::Handle, prelude::{
-        Commands, DespawnRecursive, Entity, Query, With
+    asset::{AssetServer, Handle}, prelude::{
+        Commands, DespawnRecursive, Entity, HierarchyQueryExt, Parent, Query, Res, Resource, With
     }
 };
 
+const WORLD_PATH: &str = "game.ldtk";
+
+#[derive(Resource)]
+pub struct WorldHandle(Handle<LdtkProject>);
+
+impl WorldHandle {
+    pub fn handle(&self) -> Handle<LdtkProject> { self.0.clone() }
+}
+
+pub fn load_world(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(WorldHandle(asset_server.load(WORLD_PATH)));
+}
+
+pub fn spawn_world(
+    mut commands: Commands,
+    world: Res<WorldHandle>,
+) {
+    commands.spawn(LdtkWorldBundle {
+        ldtk_handle: world.handle(),
+        ..Default::default()
+    });
+}
+
 pub fn despawn_worlds(
     mut commands: Commands,
     levels: Query<Entity, With<Handle<LdtkProject>>>
 ) {
     for level in levels.iter() {
         commands.add(DespawnRecursive{ entity: level });
-    }    
-}
\ No newline at end of file
+    }
+}
+
+/// The level an entity spawned from a level (a layer, a tile, an LDtk entity) belongs to.
+/// `None` for [Worldly](bevy_ecs_ldtk::Worldly) entities.
+pub fn owning_level<'a>(entity: Entity, parents: &Query<&Parent>, levels: &'a Query<&LevelIid>) -> Option<&'a LevelIid> {
+    parents.iter_ancestors(entity).find_map(|ancestor| levels.get(ancestor).ok())
+}
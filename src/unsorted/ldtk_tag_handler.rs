@@ -1,39 +1,27 @@
 use bevy::{
-    app::{Plugin, PreStartup}, ecs::{
+    app::{Plugin, PreStartup, Update}, ecs::{
         component::ComponentId, world::DeferredWorld
-    }, log::warn, prelude::{BuildChildren, Bundle, Entity, SpatialBundle, Transform, World}, transform::commands, utils::default
+    }, prelude::{BuildChildren, Entity, SpatialBundle, Transform, World}
 };
 use bevy_ecs_ldtk::{TileEnumTags, TileMetadata};
-use bevy_rapier2d::prelude::Collider;
+use bevy_rapier2d::prelude::{ActiveCollisionTypes, ActiveEvents, Collider, CollisionGroups, Friction, Group, Sensor};
 
+use crate::{character::Hazard, collision::{LocalGroupNames, OneWayPlatform}};
 
-mod enums {
-    #![allow(unused)]
-    pub trait EnumId { const ID: i32; }
-    macro_rules! definitions {
-        ($(enum $name:ident: $id:literal { $($variant_name:ident = $variant_value:literal)* })*) => {$(
-            pub struct $name;
-            impl EnumId for $name { const ID: i32 = $id; }
-            impl $name {
-                $(pub const $variant_name: &'static str = $variant_value;)*
-            }
-        )*};
+use super::{
+    enums::{self, EnumId},
+    tile_diagnostics::{collect_malformed_tiles, report_malformed_tile, TileDiagnostics},
+    tile_metadata::TileMetadataParsed
+};
 
-    }
-    definitions! {
-        enum Collider: 107 {
-            SOLID = "Solid"
-            TOP_SLAB = "TopSlab"
-        }
-    }
-}
-use enums::EnumId;
-use serde::{Deserialize, Serialize};
 
 pub struct LDTKEnumTagPluginCustom;
 impl Plugin for LDTKEnumTagPluginCustom {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_systems(PreStartup, |world: &mut World| {
+        app
+            .init_resource::<TileDiagnostics>()
+            .add_systems(Update, collect_malformed_tiles)
+            .add_systems(PreStartup, |world: &mut World| {
             world
                 .register_component_hooks::<TileEnumTags>()
                 .on_add(process_tile_enumtags);
@@ -47,99 +35,90 @@ impl Plugin for LDTKEnumTagPluginCustom {
 const TILESIZE: f32 = 16.0;
 const HALF_TILESIZE: f32 = TILESIZE/2.0;
 const QUARTER_TILESIZE: f32 = HALF_TILESIZE/2.0;
+const THICK_SLAB_HALF_HEIGHT: f32 = TILESIZE*3.0/8.0; // the thick slab covers the top three quarters of the tile
 fn process_tile_enumtags(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
     let enumtags = world.get::<TileEnumTags>(entity).unwrap();
     let tags = enumtags.tags.clone(); // needs to be cloned so a world can be used to make commands. 
-    let enumid = enumtags.source_enum_uid.expect("expected enum id on ldtk TileEnumTags component!");
+    let enumid = enumtags.source_enum_uid;
     let mut commands = world.commands(); // needs to be a separate variable so it lives long enough. 
     let mut entity_commands = commands.entity(entity);
+    let mut problems = Vec::new();
+    let Some(enumid) = enumid else {
+        report_malformed_tile(&mut entity_commands, vec![format!("Enum tags {:?} without a source enum!", tags)]);
+        entity_commands.remove::<TileEnumTags>();
+        return;
+    };
     match enumid {
         enums::Collider::ID => {
-            for tag in tags.iter() { match tag as &str {
-                enums::Collider::SOLID => { entity_commands.insert(Collider::cuboid(HALF_TILESIZE, HALF_TILESIZE)); },
-                enums::Collider::TOP_SLAB => { entity_commands.with_children(|children| {
+            for tag in tags.iter() { match tag.parse::<enums::Collider>() {
+                Ok(enums::Collider::Solid) => {}, // merged into larger colliders per level by the LevelColliderPlugin
+                Ok(enums::Collider::TopSlab) => { entity_commands.with_children(|children| {
                     children.spawn((
                         SpatialBundle::from_transform(Transform::from_xyz(0.0, QUARTER_TILESIZE, 0.0)),
-                        Collider::cuboid(HALF_TILESIZE, QUARTER_TILESIZE)
+                        Collider::cuboid(HALF_TILESIZE, QUARTER_TILESIZE),
+                        CollisionGroups::new(Group::ONE_WAY_PLATFORM, Group::ALL),
+                        OneWayPlatform,
                     ));
                 });},
-                _ => warn!("Unknown tag attached to {}! EnumId: {:?}; Tags: {:?}", entity, enumid, tags)
+                Ok(enums::Collider::TopSlabThick) => { entity_commands.with_children(|children| {
+                    children.spawn((
+                        SpatialBundle::from_transform(Transform::from_xyz(0.0, HALF_TILESIZE - THICK_SLAB_HALF_HEIGHT, 0.0)),
+                        Collider::cuboid(HALF_TILESIZE, THICK_SLAB_HALF_HEIGHT),
+                        CollisionGroups::new(Group::ONE_WAY_PLATFORM, Group::ALL),
+                        OneWayPlatform,
+                    ));
+                });},
+                Err(()) => problems.push(format!("Unknown tag {:?}! EnumId: {:?}; Tags: {:?}", tag, enumid, tags))
             }};
         }
-        _ => warn!("Unknown enum attached to {}! EnumId: {:?}; Tags: {:?}", entity, enumid, tags)
+        _ => problems.push(format!("Unknown enum! EnumId: {:?}; Tags: {:?}", enumid, tags))
     };
+    report_malformed_tile(&mut entity_commands, problems);
     entity_commands.remove::<TileEnumTags>();
 }
 
-mod serde_defaults {
-    pub fn one() -> f32 {1.0}
-    pub fn half() -> f32 {0.5}
-} pub use serde_defaults::*;
-
-#[derive(Serialize, Deserialize)]
-struct PointData;
-
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-enum ShapeData {
-    Square {
-        #[serde(default = "one")] width: f32,
-        #[serde(default = "one")] height: f32,
-    },
-    Circle { 
-        #[serde(default = "half")] radius: f32,
-    }
-}
-
-impl ShapeData {
-    pub fn collider(&self) -> Collider {
-        match self {
-            ShapeData::Square { width, height } => Collider::cuboid(0.5*width, 0.5*height),
-            ShapeData::Circle { radius } => Collider::ball(*radius),
-        }
-    }
-}
-
-
-
-#[derive(Serialize, Deserialize, Debug)]
-enum PivotData {
-    Offset {
-        #[serde(default)] x: f32,
-        #[serde(default)] y: f32,
-    },
-    #[serde(untagged)] None,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct ColliderData {
-    shape: ShapeData,
-    pivot: PivotData,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct TileMetadataParsed {
-    collider: ColliderData
-}
-
-impl TileMetadataParsed {
-    fn components(&self) -> impl Bundle {
-        self.collider.shape.collider()
-    } 
-}
-
-
 fn process_tile_metadata(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
-    let mut metadata = world.get::<TileMetadata>(entity).unwrap().data.split_whitespace().collect::<String>();
-    if !metadata.starts_with('{') { 
-        metadata.insert(0, '{');    
-        metadata.push('}'); 
-    }
-
-    let metadata = serde_json::from_str::<TileMetadataParsed>(&metadata).expect("Parsing tile metadata went wrong!");
+    let data = world.get::<TileMetadata>(entity).unwrap().data.clone();
+    let parsed = TileMetadataParsed::parse(&data);
     let mut commands = world.commands();
     let mut entity_commands = commands.entity(entity);
+    let mut problems = Vec::new();
+
+    let metadata = match parsed {
+        Ok(metadata) => metadata,
+        Err(error) => {
+            // a broken tile is most likely meant to be solid
+            entity_commands.insert(Collider::cuboid(HALF_TILESIZE, HALF_TILESIZE));
+            report_malformed_tile(&mut entity_commands, vec![format!("Couldn't parse metadata {:?}: {}", data, error)]);
+            entity_commands.remove::<TileMetadata>();
+            return;
+        }
+    };
 
-    entity_commands.insert(metadata.components());
+    if let Some(collider) = &metadata.collider {
+        match collider.shape.collider() {
+            Some(shape) => { entity_commands.insert(Collider::compound(vec![(collider.pivot.offset(), 0.0, shape)])); },
+            None => {
+                entity_commands.insert(Collider::cuboid(HALF_TILESIZE, HALF_TILESIZE));
+                problems.push(format!("Polygon without area, falling back to a square! Metadata: {:?}", metadata));
+            },
+        }
+        if collider.sensor {
+            entity_commands.insert((Sensor, ActiveEvents::COLLISION_EVENTS, ActiveCollisionTypes::all()));
+        }
+        if let Some(group) = collider.group {
+            match Group::from_bits(1u32.checked_shl(group.wrapping_sub(1)).unwrap_or_default()) {
+                Some(group) if !group.is_empty() => { entity_commands.insert(CollisionGroups::new(group, Group::ALL)); },
+                _ => problems.push(format!("Invalid collision group {}, it should be between 1 and 32!", group)),
+            }
+        }
+        if let Some(friction) = collider.friction {
+            entity_commands.insert(Friction::coefficient(friction));
+        }
+    }
+    if let Some(damage) = metadata.hazard {
+        entity_commands.insert(Hazard { damage });
+    }
+    report_malformed_tile(&mut entity_commands, problems);
     entity_commands.remove::<TileMetadata>();
-}
\ No newline at end of file
+}
@@ -2,10 +2,16 @@ mod promise;
 mod id;
 mod uid;
 mod ldtk_tag_handler;
+mod ldtk_definitions;
+mod tile_diagnostics;
+mod tile_metadata;
 
 pub mod ldtk_level_handler;
+pub mod ldtk_coords;
 
 pub use promise::{Promise, PromiseProcedure, BevyPromiseResolver};
 pub use id::Id;
 pub use uid::Uid;
-pub use ldtk_tag_handler::LDTKEnumTagPluginCustom;
\ No newline at end of file
+pub use ldtk_tag_handler::LDTKEnumTagPluginCustom;
+pub use ldtk_definitions::{enums, entities, int_grid};
+pub use tile_diagnostics::TileDiagnostics;
\ No newline at end of file
@@ -0,0 +1,74 @@
+use bevy::{
+    asset::Assets,
+    ecs::system::EntityCommands,
+    log::warn,
+    math::IVec2,
+    prelude::{Commands, Component, Entity, Parent, Query, Res, ResMut, Resource, Transform}
+};
+use bevy_ecs_ldtk::{assets::LdtkProject, prelude::LayerMetadata, LevelIid};
+
+use super::ldtk_level_handler::WorldHandle;
+
+/// Every tile with custom data or enum tags the game couldn't understand, shown in the debug overlay.
+#[derive(Resource, Default, Debug)]
+pub struct TileDiagnostics {
+    pub errors: Vec<TileError>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileError {
+    pub level: String,
+    pub layer: String,
+    /// LDtk grid coordinate of the tile (y pointing down).
+    pub grid: IVec2,
+    pub message: String,
+}
+
+/// The problems of a tile, waiting until the tile is part of a layer so they can be reported with its location.
+#[derive(Component, Debug)]
+pub(super) struct MalformedTile(Vec<String>);
+
+pub(super) fn report_malformed_tile(entity_commands: &mut EntityCommands, messages: Vec<String>) {
+    if messages.is_empty() { return; }
+    for message in messages.iter() {
+        warn!("Malformed tile {}: {}", entity_commands.id(), message);
+    }
+    entity_commands.insert(MalformedTile(messages));
+}
+
+pub(super) fn collect_malformed_tiles(
+    mut commands: Commands,
+    tiles: Query<(Entity, &MalformedTile, &Transform, &Parent)>,
+    layers: Query<(&LayerMetadata, &Parent)>,
+    levels: Query<&LevelIid>,
+    world: Res<WorldHandle>,
+    projects: Res<Assets<LdtkProject>>,
+    mut diagnostics: ResMut<TileDiagnostics>,
+) {
+    for (entity, malformed, transform, parent) in tiles.iter() {
+        let Ok((layer, layer_parent)) = layers.get(parent.get()) else { continue; };
+        let level = levels
+            .get(layer_parent.get())
+            .ok()
+            .and_then(|iid| projects
+                .get(&world.handle())?
+                .json_data()
+                .levels
+                .iter()
+                .find(|level| level.iid == *iid.get())
+                .map(|level| level.identifier.clone())
+            )
+            .unwrap_or_else(|| String::from("unknown level"));
+
+        let cell = (transform.translation.truncate() / layer.grid_size as f32).floor().as_ivec2();
+        let grid = IVec2::new(cell.x, layer.c_hei - 1 - cell.y);
+        for message in malformed.0.iter() {
+            let error = TileError { level: level.clone(), layer: layer.identifier.clone(), grid, message: message.clone() };
+            // respawned levels report the same tiles again
+            if !diagnostics.errors.contains(&error) {
+                diagnostics.errors.push(error);
+            }
+        }
+        commands.entity(entity).remove::<MalformedTile>();
+    }
+}
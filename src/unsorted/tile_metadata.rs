@@ -0,0 +1,189 @@
+//! The custom data of tiles, written in the LDtk tileset editor.
+//!
+//! This module only depends on external crates, so the `ldtk_check` binary can include it as well.
+
+use bevy::math::Vec2;
+use bevy_rapier2d::prelude::Collider;
+use serde::{Deserialize, Serialize};
+
+const TILESIZE: f32 = 16.0;
+
+mod serde_defaults {
+    pub fn one() -> f32 {1.0}
+    pub fn half() -> f32 {0.5}
+} pub use serde_defaults::*;
+
+#[derive(Serialize, Deserialize)]
+struct PointData;
+
+/// Sizes and positions in tile metadata are in tiles, relative to the center of the tile.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum ShapeData {
+    Square {
+        #[serde(default = "one")] width: f32,
+        #[serde(default = "one")] height: f32,
+    },
+    Circle { 
+        #[serde(default = "half")] radius: f32,
+    },
+    /// Upright capsule, `height` includes the rounded ends.
+    Capsule {
+        #[serde(default = "one")] height: f32,
+        #[serde(default = "half")] radius: f32,
+    },
+    /// Convex hull around the points.
+    Polygon {
+        points: Vec<[f32; 2]>,
+    },
+    /// Triangle rising `rise` over `run` from the bottom left corner of the tile, `flip` makes it rise to the left.
+    Slope {
+        #[serde(default = "one")] rise: f32,
+        #[serde(default = "one")] run: f32,
+        #[serde(default)] flip: bool,
+    },
+}
+
+impl ShapeData {
+    pub fn collider(&self) -> Option<Collider> {
+        let collider = match self {
+            ShapeData::Square { width, height } => Collider::cuboid(0.5*width*TILESIZE, 0.5*height*TILESIZE),
+            ShapeData::Circle { radius } => Collider::ball(radius*TILESIZE),
+            ShapeData::Capsule { height, radius } => Collider::capsule_y((0.5*height - radius).max(0.0)*TILESIZE, radius*TILESIZE),
+            ShapeData::Polygon { points } => {
+                let points = points.iter().map(|[x, y]| Vec2::new(*x, *y) * TILESIZE).collect::<Vec<_>>();
+                return Collider::convex_hull(&points);
+            },
+            ShapeData::Slope { rise, run, flip } => {
+                let direction = if *flip { -1.0 } else { 1.0 };
+                let foot = Vec2::new(-0.5 * direction, -0.5) * TILESIZE;
+                Collider::triangle(
+                    foot, 
+                    foot + Vec2::new(run * direction, 0.0) * TILESIZE, 
+                    foot + Vec2::new(run * direction, *rise) * TILESIZE
+                )
+            },
+        };
+        Some(collider)
+    }
+}
+
+/// Offset of the collider from the center of the tile, in tiles.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub enum PivotData {
+    #[serde(alias = "offset")]
+    Offset {
+        #[serde(default)] x: f32,
+        #[serde(default)] y: f32,
+    },
+    #[default]
+    #[serde(untagged)] None,
+}
+
+impl PivotData {
+    pub fn offset(&self) -> Vec2 {
+        match self {
+            PivotData::Offset { x, y } => Vec2::new(*x, *y) * TILESIZE,
+            PivotData::None => Vec2::ZERO,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ColliderData {
+    pub shape: ShapeData,
+    #[serde(default)] pub pivot: PivotData,
+    #[serde(default)] pub sensor: bool,
+    /// Collision group (1 to 32) the collider is a member of, it is a member of all groups when not set.
+    #[serde(default)] pub group: Option<u32>,
+    #[serde(default)] pub friction: Option<f32>,
+}
+
+/// The custom data of a tile, written in the LDtk tileset editor, like `{"collider": {"shape": {"slope": {"rise": 1, "run": 2}}}}`.
+///
+/// Sizes and offsets are in tiles, so the same metadata works for any tile size.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TileMetadataParsed {
+    #[serde(default)] pub collider: Option<ColliderData>,
+    /// Damage done to the player touching the tile, like spikes.
+    #[serde(default)] pub hazard: Option<u32>,
+}
+
+impl TileMetadataParsed {
+    /// Whitespace is ignored and the outer braces may be left out, so `"collider": { "shape": { "square": {} } }` is valid.
+    pub fn parse(data: &str) -> Result<Self, serde_json::Error> {
+        let mut metadata = data.split_whitespace().collect::<String>();
+        if !metadata.starts_with('{') { 
+            metadata.insert(0, '{');    
+            metadata.push('}'); 
+        }
+        serde_json::from_str(&metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collider(data: &str) -> ColliderData {
+        TileMetadataParsed::parse(data).unwrap().collider.unwrap()
+    }
+
+    #[test]
+    fn outer_braces_and_whitespace_are_optional() {
+        for data in ["\"hazard\": 2", "{\"hazard\":2}", " {\n  \"hazard\" : 2\n} "] {
+            let metadata = TileMetadataParsed::parse(data).unwrap();
+            assert_eq!(metadata.hazard, Some(2), "{}", data);
+            assert!(metadata.collider.is_none(), "{}", data);
+        }
+    }
+
+    #[test]
+    fn empty_metadata_has_no_properties() {
+        let metadata = TileMetadataParsed::parse("").unwrap();
+        assert!(metadata.collider.is_none() && metadata.hazard.is_none());
+    }
+
+    #[test]
+    fn shapes_fill_the_tile_by_default() {
+        assert!(matches!(collider(r#""collider": {"shape": {"square": {}}}"#).shape, ShapeData::Square { width: 1.0, height: 1.0 }));
+        assert!(matches!(collider(r#""collider": {"shape": {"circle": {}}}"#).shape, ShapeData::Circle { radius: 0.5 }));
+        assert!(matches!(collider(r#""collider": {"shape": {"capsule": {}}}"#).shape, ShapeData::Capsule { height: 1.0, radius: 0.5 }));
+        assert!(matches!(collider(r#""collider": {"shape": {"slope": {}}}"#).shape, ShapeData::Slope { rise: 1.0, run: 1.0, flip: false }));
+    }
+
+    #[test]
+    fn collider_properties() {
+        let collider = collider(r#""collider": {"shape": {"square": {"height": 0.25}}, "pivot": {"offset": {"y": 0.375}}, "sensor": true, "group": 3, "friction": 0.5}"#);
+        assert!(matches!(collider.shape, ShapeData::Square { width: 1.0, height: 0.25 }));
+        assert_eq!(collider.pivot.offset(), Vec2::new(0.0, 6.0));
+        assert!(collider.sensor);
+        assert_eq!(collider.group, Some(3));
+        assert_eq!(collider.friction, Some(0.5));
+    }
+
+    #[test]
+    fn colliders_are_centered_and_solid_by_default() {
+        let collider = collider(r#""collider": {"shape": {"polygon": {"points": [[0, 0], [0.5, 0], [0, 0.5]]}}}"#);
+        assert!(matches!(collider.pivot, PivotData::None));
+        assert_eq!(collider.pivot.offset(), Vec2::ZERO);
+        assert!(!collider.sensor);
+        assert_eq!(collider.group, None);
+        assert_eq!(collider.friction, None);
+    }
+
+    #[test]
+    fn sizes_are_in_tiles() {
+        let cuboid = collider(r#""collider": {"shape": {"square": {"width": 0.5}}}"#).shape.collider().unwrap();
+        assert_eq!(cuboid.as_cuboid().unwrap().half_extents(), Vec2::new(4.0, 8.0));
+    }
+
+    #[test]
+    fn malformed_metadata_is_an_error() {
+        // keys have to be quoted
+        assert!(TileMetadataParsed::parse("square: {}").is_err());
+        assert!(TileMetadataParsed::parse(r#""collider": {"shape": {"hexagon": {}}}"#).is_err());
+        assert!(TileMetadataParsed::parse(r#""collider": {}"#).is_err());
+        assert!(TileMetadataParsed::parse(r#""hazard": -1"#).is_err());
+    }
+}